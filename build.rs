@@ -0,0 +1,55 @@
+//! Collects the solutions in `src/bin` into a registry for the `registry` binary, see
+//! `template::registry`. Only that binary includes them, so that a day that does not compile
+//! does not break the main binary.
+use std::path::Path;
+use std::{env, fs};
+
+fn main() {
+    let bin_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bin");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // solutions are named `DD.rs` for the year of the flat layout and `YYYY-DD.rs` for other years.
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .ok()
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let module = match stem.split_once('-') {
+                        Some((year, day)) if is_number(year, 4) && is_number(day, 2) => {
+                            format!("year_{year}_day_{day}")
+                        }
+                        None if is_number(&stem, 2) => format!("day_{stem}"),
+                        _ => return None,
+                    };
+                    (path.extension()? == "rs").then(|| (module, path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    days.sort();
+
+    let mut out = String::from("// @generated by build.rs from the solutions in src/bin.\n\n");
+
+    // solution tests already run as part of each day's binary.
+    for (module, path) in &days {
+        out.push_str(&format!(
+            "#[cfg(not(test))]\n#[path = {path:?}]\nmod {module};\n\n"
+        ));
+    }
+
+    out.push_str("#[cfg(not(test))]\npub const SOLUTIONS: &[Solution] = &[\n");
+    for (module, _) in &days {
+        out.push_str(&format!("    {module}::SOLUTION,\n"));
+    }
+    out.push_str("];\n\n#[cfg(test)]\npub const SOLUTIONS: &[Solution] = &[];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+}
+
+fn is_number(s: &str, digits: usize) -> bool {
+    s.len() == digits && s.chars().all(|c| c.is_ascii_digit())
+}
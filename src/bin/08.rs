@@ -68,7 +68,8 @@ impl Node {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let map = Map::parse(input);
+    let map = advent_of_code::span!("parse", Map::parse(input));
+    let _span = advent_of_code::template::spans::span("solve");
     let mut count = 0;
     let mut current = "AAA".to_string();
     let mut iter = map.instructions.iter().cycle();
@@ -87,7 +88,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let map = Map::parse(input);
+    let map = advent_of_code::span!("parse", Map::parse(input));
+    let _span = advent_of_code::template::spans::span("solve");
 
     let starts = map
         .nodes
@@ -145,35 +147,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(
-            "RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)
-",
-        );
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", YEAR, DAY, 1,
+        ));
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(
-            "LR
-
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)",
-        );
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", YEAR, DAY, 2,
+        ));
         assert_eq!(result, Some(6));
     }
 }
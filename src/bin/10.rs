@@ -255,7 +255,8 @@ fn parse(input: &str) -> (Vec<Vec<Tile>>, Coord) {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (map, start) = parse(input);
+    let (map, start) = advent_of_code::span!("parse", parse(input));
+    let _span = advent_of_code::template::spans::span("solve");
     let loop_coords = build_loop(start, &map);
     Some(loop_coords.len() / 2)
 }
@@ -297,7 +298,8 @@ fn clean_map(start: Coord, loop_coords: &HashSet<Coord>, map: Vec<Vec<Tile>>) ->
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (map, start) = parse(input);
+    let (map, start) = advent_of_code::span!("parse", parse(input));
+    let _span = advent_of_code::template::spans::span("solve");
     let loop_coords = build_loop(start, &map);
     let map = clean_map(start, &loop_coords, map);
     let mut inside = false;
@@ -322,29 +324,23 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(
-            ".....
-.S-7.
-.|.|.
-.L-J.
-.....",
-        );
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", YEAR, DAY, 1,
+        ));
         assert_eq!(result, Some(4));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(
-            "...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-...........",
-        );
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", YEAR, DAY, 2,
+        ));
+        assert_eq!(result, Some(4));
+    }
+
+    #[test]
+    fn test_part_two_squeezed() {
+        let result = part_two(&advent_of_code::template::read_example(YEAR, DAY, "b"));
         assert_eq!(result, Some(4));
     }
 }
@@ -91,7 +91,8 @@ fn empty_cols(grid: &[Vec<Tile>]) -> Vec<usize> {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let grid = parse_input(input);
+    let grid = advent_of_code::span!("parse", parse_input(input));
+    let _span = advent_of_code::template::spans::span("solve");
     let galaxies = galaxy_coordinates(&grid, 2);
     let mut sum = 0;
     for i in 0..galaxies.len() {
@@ -104,7 +105,8 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let grid = parse_input(input);
+    let grid = advent_of_code::span!("parse", parse_input(input));
+    let _span = advent_of_code::template::spans::span("solve");
     let galaxies = galaxy_coordinates(&grid, 1_000_000);
     let mut sum = 0;
     for i in 0..galaxies.len() {
@@ -121,13 +123,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(374));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(82000210));
     }
 }
@@ -114,6 +114,121 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A set of days, e.g. a selection of days to run.
+///
+/// # Parsing
+/// A set parses from a comma-separated list of days and inclusive ranges.
+///
+/// ```
+/// # use advent_of_code::{Day, DaySet};
+/// let days: DaySet = "1-3,8".parse().unwrap();
+/// let days: Vec<u8> = days.iter().map(Day::into_inner).collect();
+/// assert_eq!(days, [1, 2, 3, 8]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// A set containing every day of advent.
+    #[must_use]
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    #[must_use]
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.0;
+    }
+
+    pub fn remove(&mut self, day: Day) {
+        self.0 &= !(1 << day.0);
+    }
+
+    #[must_use]
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.0) != 0
+    }
+
+    /// Returns the days that are in this set but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &DaySet) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    #[must_use]
+    pub fn is_all(&self) -> bool {
+        *self == Self::all()
+    }
+
+    /// Iterates over the days in this set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|day| self.contains(*day))
+    }
+}
+
+impl Default for DaySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self::empty();
+        iter.into_iter().for_each(|day| set.insert(day));
+        set
+    }
+}
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::empty();
+
+        for item in s.split(',').map(str::trim) {
+            let invalid = || DaySetFromStrError(item.to_string());
+
+            if let Some((start, end)) = item.split_once('-') {
+                let start: Day = start.trim().parse().map_err(|_| invalid())?;
+                let end: Day = end.trim().parse().map_err(|_| invalid())?;
+                if start > end {
+                    return Err(invalid());
+                }
+                all_days()
+                    .filter(|day| *day >= start && *day <= end)
+                    .for_each(|day| set.insert(day));
+            } else {
+                set.insert(item.parse().map_err(|_| invalid())?);
+            }
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`].
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid day selection `{}`, expecting days or ranges between 1 and 25, e.g. `1-5,8`",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -132,9 +247,9 @@ macro_rules! day {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, DaySet};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +282,34 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn parses_day_sets() {
+        let days = |s: &str| -> Vec<u8> {
+            s.parse::<DaySet>()
+                .unwrap()
+                .iter()
+                .map(Day::into_inner)
+                .collect()
+        };
+
+        assert_eq!(days("1-5,8,10"), [1, 2, 3, 4, 5, 8, 10]);
+        assert_eq!(days("25"), [25]);
+        assert_eq!(days("3, 1-2"), [1, 2, 3]);
+        assert!("0".parse::<DaySet>().is_err());
+        assert!("5-3".parse::<DaySet>().is_err());
+        assert!("1-26".parse::<DaySet>().is_err());
+        assert!("".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn day_set_difference() {
+        let days: DaySet = "1-5".parse().unwrap();
+        let skip: DaySet = "5".parse().unwrap();
+        let days: Vec<u8> = days.difference(&skip).iter().map(Day::into_inner).collect();
+        assert_eq!(days, [1, 2, 3, 4]);
+        assert!(DaySet::default().is_all());
+    }
 }
 
 /* -------------------------------------------------------------------------- */
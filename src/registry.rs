@@ -0,0 +1,14 @@
+/// The `registry` binary, which runs `all` with every solution in `src/bin` compiled in, so that
+/// days run in-process. `all` builds and invokes it, see [`advent_of_code::template::registry`].
+use advent_of_code::template::cli;
+
+/// The solutions in `src/bin`, collected by `build.rs`.
+mod solutions {
+    use advent_of_code::template::registry::Solution;
+
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+fn main() {
+    cli::run(Some(solutions::SOLUTIONS));
+}
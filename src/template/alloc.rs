@@ -0,0 +1,189 @@
+/// Opt-in heap allocation accounting for solution parts.
+/// When the `alloc_stats` feature is enabled, a counting global allocator wraps the system allocator.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde_json::{json, Value};
+
+#[cfg(feature = "alloc_stats")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static BASELINE: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts allocations while delegating to [`System`].
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn on_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let live = LIVE.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn on_dealloc(size: usize) {
+        LIVE.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::on_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::on_dealloc(layout.size());
+            Self::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocation counters for a single run of a part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// Highest amount of live heap memory above what was live when counting started.
+    pub peak_bytes: u64,
+}
+
+impl AllocStats {
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        json!({
+            "allocations": self.allocations,
+            "bytes": self.bytes,
+            "peak_bytes": self.peak_bytes,
+        })
+    }
+
+    #[must_use]
+    pub fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            allocations: value.get("allocations")?.as_u64()?,
+            bytes: value.get("bytes")?.as_u64()?,
+            peak_bytes: value.get("peak_bytes")?.as_u64()?,
+        })
+    }
+}
+
+/// Whether the counting allocator is compiled into this binary.
+#[must_use]
+pub fn is_enabled() -> bool {
+    cfg!(feature = "alloc_stats")
+}
+
+/// Resets the counters before running a part.
+pub fn reset() {
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    let live = LIVE.load(Ordering::Relaxed);
+    BASELINE.store(live, Ordering::Relaxed);
+    PEAK.store(live, Ordering::Relaxed);
+}
+
+/// Reads the counters since the last [`reset`], or [`None`] if allocation accounting is disabled.
+#[must_use]
+pub fn snapshot() -> Option<AllocStats> {
+    if !is_enabled() {
+        return None;
+    }
+
+    Some(AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: PEAK
+            .load(Ordering::Relaxed)
+            .saturating_sub(BASELINE.load(Ordering::Relaxed)),
+    })
+}
+
+/// Formats a byte count with a binary unit, e.g. `4.0 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[must_use]
+pub fn format_alloc_stats(stats: &AllocStats) -> String {
+    format!(
+        "{} allocs · {} · {} peak",
+        stats.allocations,
+        format_bytes(stats.bytes),
+        format_bytes(stats.peak_bytes)
+    )
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(4096), "4.0 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 / 2), "1.5 MiB");
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let stats = AllocStats {
+            allocations: 3,
+            bytes: 4096,
+            peak_bytes: 1024,
+        };
+        assert_eq!(AllocStats::from_json(&stats.to_json()), Some(stats));
+    }
+
+    #[cfg(feature = "alloc_stats")]
+    #[test]
+    fn counts_allocations() {
+        super::reset();
+        let v: Vec<u64> = std::hint::black_box(Vec::with_capacity(128));
+        let stats = super::snapshot().unwrap();
+        drop(v);
+        assert!(stats.allocations >= 1);
+        assert!(stats.bytes >= 1024);
+    }
+}
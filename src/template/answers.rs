@@ -0,0 +1,250 @@
+/// Module that stores known correct answers in `data/answers/DD.toml`, and the expected answers of
+/// examples next to the example files, e.g. `data/examples/DD.toml` for `data/examples/DD.txt`.
+/// The files use a small subset of TOML: one `partN = <answer>` line per part, where the answer is
+/// a basic string (`"6440"`), a literal string (`'6440'`) or an integer (`6440`), and comments.
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use crate::template::paths;
+use crate::{Day, Year};
+use serde_json::Value;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "could not parse answers file: {msg}"),
+            Error::IO(e) => write!(f, "could not access answers file: {e}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: String) {
+        match part {
+            1 => self.part_1 = Some(answer),
+            2 => self.part_2 = Some(answer),
+            _ => {}
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let mut answers = Self::default();
+
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| Error::Parser(format!("expected `key = value`, got `{line}`")))?;
+
+            let value = parse_value(value).ok_or_else(|| {
+                Error::Parser(format!(
+                    "expected a string or an integer, got `{}`",
+                    value.trim()
+                ))
+            })?;
+
+            match key.trim() {
+                "part1" => answers.part_1 = Some(value),
+                "part2" => answers.part_2 = Some(value),
+                key => return Err(Error::Parser(format!("unknown key `{key}`"))),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/// Parses a TOML string or integer, optionally followed by a comment.
+/// Integers are returned in their canonical form, e.g. `1_000` as `1000`.
+fn parse_value(value: &str) -> Option<String> {
+    let value = value.trim();
+    let is_comment = |rest: &str| {
+        let rest = rest.trim();
+        rest.is_empty() || rest.starts_with('#')
+    };
+
+    // literal strings have no escape sequences.
+    if let Some(rest) = value.strip_prefix('\'') {
+        let (s, rest) = rest.split_once('\'')?;
+        return is_comment(rest).then(|| s.to_string());
+    }
+
+    // TOML basic strings share their escape sequences with JSON strings.
+    if value.starts_with('"') {
+        let mut escaped = false;
+        let end = value.char_indices().skip(1).find_map(|(i, c)| match c {
+            '"' if !escaped => Some(i),
+            _ => {
+                escaped = c == '\\' && !escaped;
+                None
+            }
+        })?;
+        return match serde_json::from_str(&value[..=end]) {
+            Ok(s) if is_comment(&value[end + 1..]) => Some(s),
+            _ => None,
+        };
+    }
+
+    let number = value
+        .split_once('#')
+        .map_or(value, |(number, _)| number)
+        .trim();
+    let (sign, digits) = match number.strip_prefix(['+', '-']) {
+        Some(digits) => (if number.starts_with('-') { "-" } else { "" }, digits),
+        None => ("", number),
+    };
+
+    // underscores may only separate digits, and only zero itself starts with a zero.
+    let is_integer = digits
+        .split('_')
+        .all(|group| !group.is_empty() && group.chars().all(|c| c.is_ascii_digit()))
+        && (digits == "0" || !digits.starts_with('0'));
+    is_integer.then(|| format!("{sign}{}", digits.replace('_', "")))
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(answer) = &self.part_1 {
+            writeln!(f, "part1 = {}", Value::from(answer.as_str()))?;
+        }
+        if let Some(answer) = &self.part_2 {
+            writeln!(f, "part2 = {}", Value::from(answer.as_str()))?;
+        }
+        Ok(())
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Option<Year>, day: Day) -> PathBuf {
+    paths::data_dir(year)
+        .join("answers")
+        .join(format!("{day}.toml"))
+}
+
+/// The expected answers of `data/examples/DD.txt`, or of `data/examples/DD-<name>.txt` when a name is given.
+#[must_use]
+pub fn get_example_path(year: Option<Year>, day: Day, name: Option<&str>) -> PathBuf {
+    let file_name = match name {
+        Some(name) => format!("{day}-{name}.toml"),
+        None => format!("{day}.toml"),
+    };
+    paths::data_dir(year).join("examples").join(file_name)
+}
+
+/// Reads the stored answers for a day. A missing file means that no answers are known yet.
+pub fn read(year: Option<Year>, day: Day) -> Result<Answers, Error> {
+    read_path(&get_path(year, day))
+}
+
+/// Reads the expected answers of an example, see [`get_example_path`].
+pub fn read_example(year: Option<Year>, day: Day, name: Option<&str>) -> Result<Answers, Error> {
+    read_path(&get_example_path(year, day, name))
+}
+
+fn read_path(path: &Path) -> Result<Answers, Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => Answers::parse(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Stores `answer` as the correct answer for one part of a day.
+pub fn record(year: Option<Year>, day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = read(year, day)?;
+    answers.set(part, answer.to_string());
+
+    let path = get_path(year, day);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, answers.to_string())?;
+    Ok(())
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn parses_answers() {
+        let answers =
+            Answers::parse("# day 8\npart1 = \"21409\"\n\npart2 = \"21165830176709\"\n").unwrap();
+        assert_eq!(answers.get(1), Some("21409"));
+        assert_eq!(answers.get(2), Some("21165830176709"));
+    }
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers = Answers::parse("part2 = \"46\"").unwrap();
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("46"));
+    }
+
+    #[test]
+    fn roundtrip() {
+        let mut answers = Answers::default();
+        answers.set(1, "multi\n\"line\"".into());
+        answers.set(2, "42".into());
+        assert_eq!(Answers::parse(&answers.to_string()).unwrap(), answers);
+    }
+
+    #[test]
+    fn parses_toml_values() {
+        let answers = Answers::parse("part1 = 42 # the answer\npart2 = 'C:\\no\\escapes'").unwrap();
+        assert_eq!(answers.get(1), Some("42"));
+        assert_eq!(answers.get(2), Some("C:\\no\\escapes"));
+
+        let value = |s: &str| Answers::parse(&format!("part1 = {s}")).map(|a| a.part_1);
+        assert_eq!(value("-1_000").unwrap().as_deref(), Some("-1000"));
+        assert_eq!(value("+7").unwrap().as_deref(), Some("7"));
+        assert_eq!(value("0").unwrap().as_deref(), Some("0"));
+        assert_eq!(
+            value("\"a \\\" # b\" # c").unwrap().as_deref(),
+            Some("a \" # b")
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_files() {
+        assert!(Answers::parse("part1 = 4 2").is_err());
+        assert!(Answers::parse("part1 = 01").is_err());
+        assert!(Answers::parse("part1 = 1__0").is_err());
+        assert!(Answers::parse("part1 = 'x").is_err());
+        assert!(Answers::parse("part1 = \"x\" y").is_err());
+        assert!(Answers::parse("part1 = abc").is_err());
+        assert!(Answers::parse("part3 = \"42\"").is_err());
+        assert!(Answers::parse("part1").is_err());
+    }
+}
@@ -0,0 +1,433 @@
+/// Module that stores benchmark results as named baselines and compares later runs against them.
+/// Baselines are kept as JSON in `data/baselines/<name>.json`.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
+
+use serde_json::{json, Value};
+
+use crate::template::cpu::Clock;
+use crate::template::readme_benchmarks::Timings;
+use crate::template::stats::{Statistic, Summary};
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "could not parse baseline: {msg}"),
+            Error::IO(e) => write!(f, "could not access baseline: {e}"),
+        }
+    }
+}
+
+/// Options of the `all` command that save or compare baselines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BaselineOptions {
+    /// Store the results of this run under the given name.
+    pub save: Option<String>,
+    /// Compare the results of this run against the baseline with the given name.
+    pub compare: Option<String>,
+    /// Relative change, in percent, above which a part counts as improved or regressed.
+    pub threshold: f64,
+    /// Exit with a non-zero code if any part regressed.
+    pub fail_on_regression: bool,
+}
+
+impl Default for BaselineOptions {
+    fn default() -> Self {
+        Self {
+            save: None,
+            compare: None,
+            threshold: 5.0,
+            fail_on_regression: false,
+        }
+    }
+}
+
+/// The benchmark result of one part in a baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub day: Day,
+    pub part: u8,
+    pub duration: Duration,
+    pub stats: Option<Summary>,
+}
+
+impl Entry {
+    /// The duration to compare, preferring `statistic` if the part was benchmarked.
+    #[must_use]
+    pub fn duration_for(&self, statistic: Statistic) -> Duration {
+        self.stats.map_or(self.duration, |s| s.get(statistic))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Baseline {
+    /// The statistic that was selected when the baseline was recorded.
+    pub statistic: Statistic,
+    /// The clock that was selected when the baseline was recorded.
+    pub clock: Clock,
+    pub entries: Vec<Entry>,
+}
+
+impl Baseline {
+    #[must_use]
+    pub fn from_timings(timings: &[Timings], statistic: Statistic, clock: Clock) -> Self {
+        let entries = timings
+            .iter()
+            .flat_map(|t| {
+                (1..=2).filter_map(|part| {
+                    t.part(part).map(|p| Entry {
+                        day: t.day,
+                        part,
+                        duration: p.duration,
+                        stats: p.stats,
+                    })
+                })
+            })
+            .collect();
+
+        Self {
+            statistic,
+            clock,
+            entries,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, day: Day, part: u8) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.day == day && e.part == part)
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let entries: Vec<Value> = self
+            .entries
+            .iter()
+            .map(|e| {
+                json!({
+                    "day": e.day.into_inner(),
+                    "part": e.part,
+                    "duration_nanos": e.duration.as_nanos(),
+                    "stats": e.stats.as_ref().map(Summary::to_json),
+                })
+            })
+            .collect();
+
+        json!({
+            "statistic": self.statistic.as_str(),
+            "clock": self.clock.as_str(),
+            "parts": entries,
+        })
+        .to_string()
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let value = serde_json::from_str::<Value>(s)?;
+        let invalid = |field: &str| Error::Parser(format!("missing or invalid field `{field}`"));
+
+        let statistic = value
+            .get("statistic")
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| invalid("statistic"))?;
+
+        // baselines saved before cpu time was measured only contain wall time.
+        let clock = match value.get("clock") {
+            None => Clock::Wall,
+            Some(v) => v
+                .as_str()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(|| invalid("clock"))?,
+        };
+
+        let entries = value
+            .get("parts")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid("parts"))?
+            .iter()
+            .map(|entry| {
+                let day = entry
+                    .get("day")
+                    .and_then(Value::as_u64)
+                    .and_then(|d| u8::try_from(d).ok())
+                    .and_then(Day::new)
+                    .ok_or_else(|| invalid("day"))?;
+
+                let part = entry
+                    .get("part")
+                    .and_then(Value::as_u64)
+                    .and_then(|p| u8::try_from(p).ok())
+                    .ok_or_else(|| invalid("part"))?;
+
+                let duration = entry
+                    .get("duration_nanos")
+                    .and_then(Value::as_u64)
+                    .map(Duration::from_nanos)
+                    .ok_or_else(|| invalid("duration_nanos"))?;
+
+                let stats = match entry.get("stats") {
+                    Some(Value::Null) | None => None,
+                    Some(v) => Some(Summary::from_json(v).ok_or_else(|| invalid("stats"))?),
+                };
+
+                Ok(Entry {
+                    day,
+                    part,
+                    duration,
+                    stats,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self {
+            statistic,
+            clock,
+            entries,
+        })
+    }
+}
+
+/// Parses the threshold of a comparison, a non-negative percentage.
+pub fn parse_threshold(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(threshold) if threshold.is_finite() && threshold >= 0.0 => Ok(threshold),
+        _ => Err(format!("`{s}` is not a non-negative percentage")),
+    }
+}
+
+/// Checks that a baseline name can be used as a file name.
+pub fn parse_name(name: &str) -> Result<String, String> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        && !name.starts_with('.');
+
+    if is_valid {
+        Ok(name.to_string())
+    } else {
+        Err(format!(
+            "`{name}` is not a valid baseline name, use letters, digits, `-`, `_` and `.`"
+        ))
+    }
+}
+
+#[must_use]
+pub fn get_path(name: &str) -> PathBuf {
+    PathBuf::from("data")
+        .join("baselines")
+        .join(format!("{name}.json"))
+}
+
+pub fn save(name: &str, baseline: &Baseline) -> Result<(), Error> {
+    let path = get_path(name);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, baseline.to_json() + "\n")?;
+    Ok(())
+}
+
+pub fn load(name: &str) -> Result<Baseline, Error> {
+    Baseline::parse(&fs::read_to_string(get_path(name))?)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+/// The timing of one part in the baseline and in the current run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Comparison {
+    /// Relative change from the baseline, in percent.
+    #[must_use]
+    pub fn percent(&self) -> f64 {
+        let before = self.before.as_secs_f64();
+        if before == 0.0 {
+            return 0.0;
+        }
+        (self.after.as_secs_f64() - before) / before * 100.0
+    }
+
+    #[must_use]
+    pub fn change(&self, threshold: f64) -> Change {
+        let percent = self.percent();
+        if percent > threshold {
+            Change::Regressed
+        } else if percent < -threshold {
+            Change::Improved
+        } else {
+            Change::Unchanged
+        }
+    }
+}
+
+/// Compares every part of the current run that also has an entry in the baseline.
+#[must_use]
+pub fn compare(baseline: &Baseline, timings: &[Timings], statistic: Statistic) -> Vec<Comparison> {
+    timings
+        .iter()
+        .flat_map(|t| {
+            (1..=2).filter_map(move |part| {
+                let current = t.part(part)?;
+                let entry = baseline.get(t.day, part)?;
+                Some(Comparison {
+                    day: t.day,
+                    part,
+                    before: entry.duration_for(statistic),
+                    after: current.stats.map_or(current.duration, |s| s.get(statistic)),
+                })
+            })
+        })
+        .collect()
+}
+
+#[must_use]
+pub fn format_comparison(comparison: &Comparison, threshold: f64) -> String {
+    let label = match comparison.change(threshold) {
+        Change::Improved => " improved",
+        Change::Regressed => " regressed",
+        Change::Unchanged => "",
+    };
+
+    format!(
+        "Day {} part {}: {:.1?} -> {:.1?} ({:+.1}%){label}",
+        comparison.day,
+        comparison.part,
+        comparison.before,
+        comparison.after,
+        comparison.percent()
+    )
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        compare, format_comparison, parse_name, parse_threshold, Baseline, Change, Comparison,
+    };
+    use crate::day;
+    use crate::template::cpu::Clock;
+    use crate::template::readme_benchmarks::{PartTiming, Timings};
+    use crate::template::stats::{Statistic, Summary};
+
+    fn timings(part_1_nanos: u64, part_2: Option<PartTiming>) -> Vec<Timings> {
+        vec![Timings {
+            day: day!(8),
+            part_1: Some(PartTiming {
+                duration: Duration::from_nanos(part_1_nanos),
+                samples: 1,
+                stats: None,
+                allocations: None,
+            }),
+            part_2,
+            total_nanos: 0.0,
+        }]
+    }
+
+    #[test]
+    fn roundtrip() {
+        let samples = [Duration::from_nanos(70), Duration::from_nanos(90)];
+        let part_2 = PartTiming {
+            duration: Duration::from_nanos(80),
+            samples: 2,
+            stats: Some(Summary::from_samples(&samples, false)),
+            allocations: None,
+        };
+        let baseline =
+            Baseline::from_timings(&timings(100, Some(part_2)), Statistic::Median, Clock::Wall);
+        assert_eq!(baseline.entries.len(), 2);
+        assert_eq!(Baseline::parse(&baseline.to_json()).unwrap(), baseline);
+    }
+
+    #[test]
+    fn detects_changes() {
+        let baseline = Baseline::from_timings(&timings(100, None), Statistic::Mean, Clock::Wall);
+
+        let comparisons = compare(&baseline, &timings(120, None), Statistic::Mean);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].change(5.0), Change::Regressed);
+        assert_eq!(comparisons[0].change(25.0), Change::Unchanged);
+
+        let comparisons = compare(&baseline, &timings(80, None), Statistic::Mean);
+        assert_eq!(comparisons[0].change(5.0), Change::Improved);
+    }
+
+    #[test]
+    fn skips_parts_without_baseline() {
+        let baseline = Baseline::from_timings(&timings(100, None), Statistic::Mean, Clock::Wall);
+        let part_2 = PartTiming {
+            duration: Duration::from_nanos(50),
+            samples: 1,
+            stats: None,
+            allocations: None,
+        };
+        assert_eq!(
+            compare(&baseline, &timings(100, Some(part_2)), Statistic::Mean).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn formats_comparison() {
+        let comparison = Comparison {
+            day: day!(8),
+            part: 2,
+            before: Duration::from_micros(10),
+            after: Duration::from_micros(12),
+        };
+        assert_eq!(
+            format_comparison(&comparison, 5.0),
+            "Day 08 part 2: 10.0µs -> 12.0µs (+20.0%) regressed"
+        );
+    }
+
+    #[test]
+    fn validates_names() {
+        assert!(parse_name("main").is_ok());
+        assert!(parse_name("pr-42_v1.2").is_ok());
+        assert!(parse_name("").is_err());
+        assert!(parse_name("../main").is_err());
+        assert!(parse_name(".hidden").is_err());
+    }
+
+    #[test]
+    fn validates_thresholds() {
+        assert_eq!(parse_threshold("2.5"), Ok(2.5));
+        assert_eq!(parse_threshold("0"), Ok(0.0));
+        assert!(parse_threshold("-5").is_err());
+        assert!(parse_threshold("inf").is_err());
+        assert!(parse_threshold("five").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+/// Builds solution binaries with cargo and locates their executables.
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::template::commands::Error;
+use crate::template::paths;
+use crate::{Day, Year};
+
+/// Name of the binary that runs `all` with every solution compiled in, see [`crate::template::registry`].
+const REGISTRY_BIN: &str = "registry";
+
+/// Builds all solution binaries with a single cargo invocation.
+/// Returns the executables of `year` that were built, by day. A day that does not compile is
+/// left out, cargo prints its errors and keeps building the other days.
+pub fn build_all(
+    year: Option<Year>,
+    is_release: bool,
+    alloc_stats: bool,
+) -> Result<Vec<(Day, PathBuf)>, Error> {
+    let (artifacts, _) = build(
+        &["--bins", "--keep-going"],
+        &[],
+        is_release,
+        alloc_stats,
+        true,
+    )?;
+    Ok(solutions(artifacts, year))
+}
+
+/// Builds the solution binary of a single day and returns its executable.
+pub fn build_day(
+    year: Option<Year>,
+    day: Day,
+    is_release: bool,
+    alloc_stats: bool,
+) -> Result<PathBuf, Error> {
+    let bin_name = paths::bin_name(year, day);
+    let (artifacts, success) = build(&["--bin", &bin_name], &[], is_release, alloc_stats, true)?;
+
+    if !success {
+        return Err(Error::Compile);
+    }
+
+    solutions(artifacts, year)
+        .into_iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, binary)| binary)
+        .ok_or_else(|| Error::Other(format!("cargo did not build a binary for day {day}")))
+}
+
+/// Builds the `registry` binary and returns its executable.
+/// Compiler errors are not printed, the binaries of each day report them on their own.
+pub fn build_registry(is_release: bool, alloc_stats: bool) -> Result<PathBuf, Error> {
+    let (artifacts, success) = build(
+        &["--bin", REGISTRY_BIN],
+        &["registry"],
+        is_release,
+        alloc_stats,
+        false,
+    )?;
+
+    if !success {
+        return Err(Error::Compile);
+    }
+
+    artifacts
+        .into_iter()
+        .find(|(name, _)| name == REGISTRY_BIN)
+        .map(|(_, binary)| binary)
+        .ok_or_else(|| Error::Other("cargo did not build the registry binary".into()))
+}
+
+/// The executables of the solutions of `year` among the artifacts of a build, by day.
+fn solutions(artifacts: Vec<(String, PathBuf)>, year: Option<Year>) -> Vec<(Day, PathBuf)> {
+    artifacts
+        .into_iter()
+        .filter_map(|(name, binary)| match paths::parse_bin_name(&name)? {
+            (y, day) if y == year => Some((day, binary)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Runs `cargo build` for `targets`, e.g. `--bins`, with the given `features` enabled.
+/// Returns the executables that were built by the name of their binary, and whether the build succeeded.
+fn build(
+    targets: &[&str],
+    features: &[&str],
+    is_release: bool,
+    alloc_stats: bool,
+    show_errors: bool,
+) -> Result<(Vec<(String, PathBuf)>, bool), Error> {
+    let mut args = vec![
+        "build",
+        "--quiet",
+        "--message-format=json-render-diagnostics",
+    ];
+    args.extend_from_slice(targets);
+
+    if is_release {
+        args.push("--release");
+    }
+
+    let mut features = features.to_vec();
+    if alloc_stats {
+        features.push("alloc_stats");
+    }
+    let features = features.join(",");
+    if !features.is_empty() {
+        args.extend(["--features", &features]);
+    }
+
+    // compiler messages are printed to stderr, artifacts are reported as json on stdout.
+    let mut cmd = Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(if show_errors {
+            Stdio::inherit()
+        } else {
+            Stdio::null()
+        })
+        .spawn()?;
+
+    let stdout = BufReader::new(
+        cmd.stdout
+            .take()
+            .ok_or_else(|| Error::Other("could not read cargo output".into()))?,
+    );
+
+    let mut artifacts = vec![];
+
+    for line in stdout.lines() {
+        if let Some(artifact) = parse_artifact(&line?) {
+            artifacts.push(artifact);
+        }
+    }
+
+    let success = cmd.wait()?.success();
+    Ok((artifacts, success))
+}
+
+/// Extracts the name and executable of a binary from a cargo `compiler-artifact` message.
+fn parse_artifact(line: &str) -> Option<(String, PathBuf)> {
+    let message: serde_json::Value = serde_json::from_str(line).ok()?;
+
+    if message.get("reason")?.as_str()? != "compiler-artifact" {
+        return None;
+    }
+
+    let name = message.get("target")?.get("name")?.as_str()?;
+    let executable = message.get("executable")?.as_str()?;
+    Some((name.to_string(), PathBuf::from(executable)))
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse_artifact, solutions};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_artifacts() {
+        let line = r#"{"reason":"compiler-artifact","package_id":"advent_of_code 0.9.2","target":{"kind":["bin"],"crate_types":["bin"],"name":"08","src_path":"/aoc/src/bin/08.rs","edition":"2021","doc":true,"doctest":false,"test":true},"profile":{"opt_level":"3","debuginfo":0,"debug_assertions":false,"overflow_checks":false,"test":false},"features":[],"filenames":["/aoc/target/release/08"],"executable":"/aoc/target/release/08","fresh":true}"#;
+        assert_eq!(
+            parse_artifact(line),
+            Some(("08".to_string(), PathBuf::from("/aoc/target/release/08")))
+        );
+
+        let library = r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code"},"executable":null}"#;
+        assert_eq!(parse_artifact(library), None);
+        assert_eq!(
+            parse_artifact(r#"{"reason":"build-finished","success":true}"#),
+            None
+        );
+    }
+
+    #[test]
+    fn selects_solutions_of_year() {
+        let artifacts = ["08", "2022-08", "registry"]
+            .map(|name| (name.to_string(), PathBuf::from(name)))
+            .to_vec();
+        assert_eq!(
+            solutions(artifacts.clone(), None),
+            vec![(day!(8), PathBuf::from("08"))]
+        );
+        assert_eq!(
+            solutions(artifacts, Some(year!(2022))),
+            vec![(day!(8), PathBuf::from("2022-08"))]
+        );
+    }
+}
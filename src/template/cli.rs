@@ -0,0 +1,294 @@
+/// Command line interface of the template, shared by the main binary and the `registry` binary.
+use std::process;
+use std::time::Duration;
+
+use crate::template::baseline::{self, BaselineOptions};
+use crate::template::commands::all::AllOptions;
+use crate::template::commands::solve::SolveOptions;
+use crate::template::commands::{all, download, read, scaffold, solve, test};
+use crate::template::export::Output;
+use crate::template::input::InputSource;
+use crate::template::paths;
+use crate::template::registry::Solution;
+use crate::template::runner::{parse_count, parse_part, parse_seconds, BenchOptions, RunOptions};
+use crate::{Day, DaySet, Year};
+
+enum AppArguments {
+    Download {
+        year: Option<Year>,
+        day: Day,
+    },
+    Read {
+        year: Option<Year>,
+        day: Day,
+        part: Option<u8>,
+    },
+    Scaffold {
+        year: Option<Year>,
+        day: Day,
+    },
+    Solve {
+        day: Day,
+        solve: SolveOptions,
+        options: RunOptions,
+        input: InputSource,
+    },
+    Test {
+        year: Option<Year>,
+        day: Day,
+        release: bool,
+        timeout: Option<Duration>,
+    },
+    All {
+        all: AllOptions,
+        options: RunOptions,
+        baseline: BaselineOptions,
+        outputs: Vec<Output>,
+    },
+}
+
+fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    let mut args = pico_args::Arguments::from_env();
+
+    let app_args = match args.subcommand()?.as_deref() {
+        Some("all") => {
+            let year = parse_year(&mut args)?;
+            let options = parse_run_options(&mut args)?;
+            let baseline = parse_baseline_options(&mut args)?;
+
+            let release = args.contains("--release");
+
+            if !options.time && (baseline.save.is_some() || baseline.compare.is_some()) {
+                eprintln!("Baselines require benchmarks, pass `--time` or use `cargo time`.");
+                process::exit(1);
+            }
+
+            let mut outputs: Vec<Output> = args.values_from_str("--output")?;
+
+            if !options.time && !outputs.is_empty() {
+                eprintln!("Outputs require benchmarks, pass `--time` or use `cargo time`.");
+                process::exit(1);
+            }
+
+            // the readme only lists the benchmarks of both parts of the year set with `AOC_YEAR`.
+            if year != paths::resolve(None) && outputs.contains(&Output::Readme) {
+                eprintln!("The readme only lists the year set with AOC_YEAR, pick another output.");
+                process::exit(1);
+            }
+
+            if options.part.is_some() && outputs.contains(&Output::Readme) {
+                eprintln!("The readme lists both parts, remove `--part` or pick another output.");
+                process::exit(1);
+            }
+
+            // optimized benchmarks update the readme, unless other outputs were requested.
+            if options.time
+                && release
+                && outputs.is_empty()
+                && year.is_none()
+                && options.part.is_none()
+            {
+                outputs.push(Output::Readme);
+            }
+
+            let days: DaySet = args.opt_value_from_str("--days")?.unwrap_or_default();
+            let skip: DaySet = args
+                .opt_value_from_str("--skip")?
+                .unwrap_or_else(DaySet::empty);
+
+            AppArguments::All {
+                all: AllOptions {
+                    year,
+                    release,
+                    alloc_stats: args.contains("--alloc-stats"),
+                    subprocess: args.contains("--subprocess"),
+                    jobs: args
+                        .opt_value_from_str(["-j", "--jobs"])?
+                        .map_or(1, |jobs: usize| jobs.max(1)),
+                    days: days.difference(&skip),
+                },
+                options,
+                baseline,
+                outputs,
+            }
+        }
+        Some("download") => AppArguments::Download {
+            year: parse_year(&mut args)?,
+            day: args.free_from_str()?,
+        },
+        Some("read") => AppArguments::Read {
+            year: parse_year(&mut args)?,
+            part: args.opt_value_from_fn("--part", parse_part)?,
+            day: args.free_from_str()?,
+        },
+        Some("scaffold") => AppArguments::Scaffold {
+            year: parse_year(&mut args)?,
+            day: args.free_from_str()?,
+        },
+        Some("test") => AppArguments::Test {
+            year: parse_year(&mut args)?,
+            day: args.free_from_str()?,
+            release: args.contains("--release"),
+            timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+        },
+        Some("solve") => {
+            let year = parse_year(&mut args)?;
+            AppArguments::Solve {
+                day: args.free_from_str()?,
+                solve: SolveOptions {
+                    year,
+                    release: args.contains("--release"),
+                    alloc_stats: args.contains("--alloc-stats"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    wait: args.contains("--wait"),
+                    watch: args.contains("--watch"),
+                    tests: args.contains("--tests"),
+                },
+                options: RunOptions {
+                    json: args.contains("--json"),
+                    ..parse_run_options(&mut args)?
+                },
+                input: parse_input_source(&mut args)?,
+            }
+        }
+        Some(x) => {
+            eprintln!("Unknown command: {x}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("No command specified.");
+            process::exit(1);
+        }
+    };
+
+    let remaining = args.finish();
+    if !remaining.is_empty() {
+        eprintln!("Warning: unknown argument(s): {remaining:?}.");
+    }
+
+    Ok(app_args)
+}
+
+/// Needs to run before the day is parsed, because the day is read as the first free argument.
+fn parse_year(args: &mut pico_args::Arguments) -> Result<Option<Year>, pico_args::Error> {
+    Ok(paths::resolve(args.opt_value_from_str("--year")?))
+}
+
+/// Needs to run after all other options of a command were parsed,
+/// because the name following `--example` is optional and read as a free argument.
+fn parse_input_source(args: &mut pico_args::Arguments) -> Result<InputSource, pico_args::Error> {
+    if let Some(path) = args.opt_value_from_str("--input")? {
+        return Ok(InputSource::File(path));
+    }
+
+    if args.contains("--stdin") {
+        return Ok(InputSource::Stdin);
+    }
+
+    if args.contains("--example") {
+        return Ok(InputSource::Example(args.opt_free_from_str()?));
+    }
+
+    Ok(InputSource::Inputs)
+}
+
+fn parse_baseline_options(
+    args: &mut pico_args::Arguments,
+) -> Result<BaselineOptions, pico_args::Error> {
+    let defaults = BaselineOptions::default();
+
+    Ok(BaselineOptions {
+        save: args.opt_value_from_fn("--save-baseline", baseline::parse_name)?,
+        compare: args.opt_value_from_fn("--baseline", baseline::parse_name)?,
+        threshold: args
+            .opt_value_from_fn("--threshold", baseline::parse_threshold)?
+            .unwrap_or(defaults.threshold),
+        fail_on_regression: args.contains("--fail-on-regression"),
+    })
+}
+
+fn parse_run_options(args: &mut pico_args::Arguments) -> Result<RunOptions, pico_args::Error> {
+    let config = BenchOptions::from_config().unwrap_or_else(|e| {
+        eprintln!("Invalid benchmark configuration: {e}");
+        process::exit(1);
+    });
+
+    let bench = BenchOptions {
+        statistic: args.opt_value_from_str("--stat")?.unwrap_or_default(),
+        reject_outliers: args.contains("--reject-outliers"),
+        budget: args
+            .opt_value_from_fn("--bench-time", parse_seconds)?
+            .unwrap_or(config.budget),
+        min_samples: args
+            .opt_value_from_fn("--min-samples", parse_count)?
+            .unwrap_or(config.min_samples),
+        max_samples: args
+            .opt_value_from_fn("--max-samples", parse_count)?
+            .unwrap_or(config.max_samples),
+        warmup: args
+            .opt_value_from_fn("--warmup", parse_count)?
+            .unwrap_or(config.warmup),
+    };
+
+    Ok(RunOptions {
+        time: args.contains("--time"),
+        json: false,
+        timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+        clock: args.opt_value_from_str("--clock")?.unwrap_or_default(),
+        part: args.opt_value_from_fn("--part", parse_part)?,
+        bench: bench.validate().unwrap_or_else(|e| {
+            eprintln!("Invalid benchmark options: {e}");
+            process::exit(1);
+        }),
+    })
+}
+
+/// Parses the arguments and runs the command. `registry` holds the solutions that were compiled
+/// into the running binary, [`None`] for the main binary, see [`crate::template::registry`].
+pub fn run(registry: Option<&[Solution]>) {
+    match parse() {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+        Ok(args) => {
+            let result = match args {
+                AppArguments::All {
+                    all,
+                    options,
+                    baseline,
+                    outputs,
+                } => all::handle(all, options, &baseline, &outputs, registry),
+                AppArguments::Download { year, day } => {
+                    download::handle(year, day);
+                    Ok(())
+                }
+                AppArguments::Read { year, day, part } => {
+                    read::handle(year, day, part);
+                    Ok(())
+                }
+                AppArguments::Scaffold { year, day } => {
+                    scaffold::handle(year, day);
+                    Ok(())
+                }
+                AppArguments::Solve {
+                    day,
+                    solve,
+                    options,
+                    input,
+                } => solve::handle(day, solve, options, &input),
+                AppArguments::Test {
+                    year,
+                    day,
+                    release,
+                    timeout,
+                } => test::handle(year, day, release, timeout),
+            };
+
+            if let Err(e) = result {
+                eprintln!("Error: {e}");
+                std::process::exit(e.exit_code());
+            }
+        }
+    };
+}
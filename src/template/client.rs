@@ -0,0 +1,444 @@
+/// Built-in client for the Advent of Code website, used to download inputs, read puzzles and
+/// submit answers. The "aoc-cli" command-line remains available as a backend, see [`Backend`].
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+use std::{env, fs, io};
+
+use crate::template::aoc_cli::{self, AocCommandError};
+use crate::template::http::{self, Request};
+use crate::template::submission::Verdict;
+use crate::template::{markdown, paths, puzzle};
+use crate::{Day, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The website asks automated tools to identify themselves, `AOC_USER_AGENT` adds a contact.
+const DEFAULT_USER_AGENT: &str = concat!("advent_of_code/", env!("CARGO_PKG_VERSION"));
+
+/// Should be added to each cooldown, as the website rounds the time that is left to wait.
+pub const COOLDOWN_MARGIN: Duration = Duration::from_secs(1);
+
+/// Gives up after this many cooldowns, so that an unexpected response can not cause a busy loop.
+const MAX_COOLDOWNS: usize = 5;
+
+#[derive(Debug)]
+pub enum Error {
+    /// No session cookie was found, see [`Session::from_env`].
+    MissingSession,
+    /// Neither `--year` nor `AOC_YEAR` selected a year.
+    MissingYear,
+    /// The website responded with an unexpected status code.
+    Status(u16),
+    /// The response to a submission contains no [`Verdict`].
+    UnexpectedResponse(String),
+    /// The description of this part is not unlocked yet.
+    LockedPart(u8),
+    Config(String),
+    IO(io::Error),
+    AocCli(AocCommandError),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found, set AOC_SESSION or create ~/.adventofcode.session"
+            ),
+            Error::MissingYear => write!(f, "no year selected, set AOC_YEAR or pass --year"),
+            Error::Status(400) => write!(f, "the session cookie is invalid or expired"),
+            Error::Status(404) => write!(f, "the puzzle is not available yet"),
+            Error::Status(status) => write!(f, "the website responded with status {status}"),
+            Error::UnexpectedResponse(message) => {
+                write!(f, "could not interpret the response: {message}")
+            }
+            Error::LockedPart(part) => write!(f, "part {part} is not unlocked yet"),
+            Error::Config(msg) => write!(f, "{msg}"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::AocCli(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<AocCommandError> for Error {
+    fn from(e: AocCommandError) -> Self {
+        Error::AocCli(e)
+    }
+}
+
+/// The session cookie that authenticates requests to the website.
+pub struct Session(String);
+
+impl Session {
+    /// Reads the session from `AOC_SESSION`, or from the file at `AOC_SESSION_FILE`.
+    /// Falls back to the files that aoc-cli reads, `~/.adventofcode.session` and
+    /// `~/.config/adventofcode.session`.
+    pub fn from_env() -> Result<Self, Error> {
+        if let Some(session) = env::var("AOC_SESSION").ok().and_then(Self::parse) {
+            return Ok(session);
+        }
+
+        let files: Vec<PathBuf> = match env::var_os("AOC_SESSION_FILE") {
+            Some(file) => vec![file.into()],
+            None => env::var_os("HOME")
+                .map(PathBuf::from)
+                .map(|home| {
+                    vec![
+                        home.join(".adventofcode.session"),
+                        home.join(".config").join("adventofcode.session"),
+                    ]
+                })
+                .unwrap_or_default(),
+        };
+
+        files
+            .iter()
+            .find_map(|file| Self::parse(fs::read_to_string(file).ok()?))
+            .ok_or(Error::MissingSession)
+    }
+
+    fn parse(session: String) -> Option<Self> {
+        let session = session.trim();
+        (!session.is_empty()).then(|| Self(session.to_string()))
+    }
+}
+
+pub struct Client {
+    base_url: String,
+    session: Session,
+    user_agent: String,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: &str, session: Session) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            user_agent: DEFAULT_USER_AGENT.into(),
+        }
+    }
+
+    /// Identifies requests with `user_agent` instead of the name and version of the template.
+    #[must_use]
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = user_agent.to_string();
+        self
+    }
+
+    /// Creates a client for the website at `AOC_BASE_URL`, `https://adventofcode.com` by default.
+    /// Requests are identified with `AOC_USER_AGENT`, which should contain a way to contact you.
+    pub fn from_env() -> Result<Self, Error> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let client = Self::new(&base_url, Session::from_env()?);
+        Ok(match env::var("AOC_USER_AGENT") {
+            Ok(user_agent) if !user_agent.trim().is_empty() => {
+                client.with_user_agent(user_agent.trim())
+            }
+            _ => client,
+        })
+    }
+
+    pub fn input(&self, year: Year, day: Day) -> Result<String, Error> {
+        self.send(Request::get(&self.day_url(year, day, "/input")))
+    }
+
+    /// Fetches the puzzle description as markdown, with the parts that are unlocked.
+    pub fn puzzle(&self, year: Year, day: Day) -> Result<String, Error> {
+        let html = self.send(Request::get(&self.day_url(year, day, "")))?;
+        Ok(puzzle::articles(&html)
+            .into_iter()
+            .map(puzzle::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Submits an answer and returns the verdict of the website.
+    pub fn submit(&self, year: Year, day: Day, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let body = http::encode_form(&[("level", &part.to_string()), ("answer", answer)]);
+        let html = self.send(Request::post(&self.day_url(year, day, "/answer"), body))?;
+        let message = puzzle::articles(&html).first().map_or_else(
+            || puzzle::to_text(&html),
+            |article| puzzle::to_text(article),
+        );
+        Verdict::parse(&message).ok_or(Error::UnexpectedResponse(message))
+    }
+
+    fn day_url(&self, year: Year, day: Day, path: &str) -> String {
+        format!("{}/{year}/day/{}{path}", self.base_url, day.into_inner())
+    }
+
+    fn send(&self, request: Request) -> Result<String, Error> {
+        let response = request
+            .header("Cookie", &format!("session={}", self.session.0))
+            .header("User-Agent", &self.user_agent)
+            .send()?;
+
+        match response.status {
+            200 => Ok(response.body),
+            status => Err(Error::Status(status)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Selects how the template talks to the website, configured with `AOC_BACKEND`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backend {
+    /// The built-in [`Client`].
+    #[default]
+    Native,
+    /// The externally installed "aoc-cli" command-line.
+    AocCli,
+}
+
+impl Backend {
+    pub fn from_env() -> Result<Self, Error> {
+        match env::var("AOC_BACKEND") {
+            Ok(value) if !value.trim().is_empty() => value
+                .trim()
+                .parse()
+                .map_err(|e: BackendFromStrError| Error::Config(format!("AOC_BACKEND: {e}"))),
+            _ => Ok(Self::default()),
+        }
+    }
+}
+
+impl FromStr for Backend {
+    type Err = BackendFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "native" => Ok(Backend::Native),
+            "aoc-cli" => Ok(Backend::AocCli),
+            _ => Err(BackendFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Backend`].
+#[derive(Debug)]
+pub struct BackendFromStrError;
+
+impl std::error::Error for BackendFromStrError {}
+
+impl Display for BackendFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting `native` or `aoc-cli`")
+    }
+}
+
+fn aoc_cli_check() -> Result<(), Error> {
+    aoc_cli::check().map_err(|_| {
+        Error::Config(
+            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
+                .into(),
+        )
+    })
+}
+
+fn require_year(year: Option<Year>) -> Result<Year, Error> {
+    paths::effective_year(year).ok_or(Error::MissingYear)
+}
+
+/// Downloads the input and the puzzle description of a day into `data`.
+pub fn download(year: Option<Year>, day: Day) -> Result<(), Error> {
+    if Backend::from_env()? == Backend::AocCli {
+        aoc_cli_check()?;
+        aoc_cli::download(year, day)?;
+        return Ok(());
+    }
+
+    let input_path = paths::input_path(year, day);
+    let puzzle_path = paths::puzzle_path(year, day);
+
+    let client = Client::from_env()?;
+    let year = require_year(year)?;
+    let input = client.input(year, day)?;
+    let puzzle = client.puzzle(year, day)?;
+
+    for path in [&input_path, &puzzle_path] {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+    }
+    fs::write(&input_path, input)?;
+    fs::write(&puzzle_path, puzzle + "\n")?;
+
+    println!("---");
+    println!(
+        "🎄 Successfully wrote input to \"{}\".",
+        input_path.display()
+    );
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+    Ok(())
+}
+
+/// Renders the puzzle description of a day in the terminal, or only the description of `part`.
+/// The downloaded description is shown once it contains both parts. Until then, it is fetched
+/// again to include a part unlocked since the download, the downloaded copy is shown if that fails.
+pub fn read(year: Option<Year>, day: Day, part: Option<u8>) -> Result<(), Error> {
+    if Backend::from_env()? == Backend::AocCli {
+        if part.is_some() {
+            return Err(Error::Config(
+                "aoc-cli can not show a single part, set AOC_BACKEND=native to use `--part`".into(),
+            ));
+        }
+        aoc_cli_check()?;
+        aoc_cli::read(year, day)?;
+        return Ok(());
+    }
+
+    let puzzle_path = paths::puzzle_path(year, day);
+    let cached = fs::read_to_string(&puzzle_path).ok();
+
+    let puzzle = match cached {
+        Some(puzzle) if puzzle::part(&puzzle, 2).is_some() => puzzle,
+        cached => {
+            match Client::from_env().and_then(|client| client.puzzle(require_year(year)?, day)) {
+                Ok(puzzle) => {
+                    if let Some(dir) = puzzle_path.parent() {
+                        fs::create_dir_all(dir)?;
+                    }
+                    fs::write(&puzzle_path, puzzle.clone() + "\n")?;
+                    puzzle
+                }
+                Err(e) => match cached {
+                    Some(puzzle) => {
+                        eprintln!(
+                            "Could not fetch the puzzle ({e}), showing \"{}\".",
+                            puzzle_path.display()
+                        );
+                        puzzle
+                    }
+                    None => return Err(e),
+                },
+            }
+        }
+    };
+
+    let section = match part {
+        Some(part) => puzzle::part(&puzzle, part).ok_or(Error::LockedPart(part))?,
+        None => &puzzle,
+    };
+    println!("{}", markdown::render(section, markdown::terminal_width()));
+    Ok(())
+}
+
+/// Submits the answer of a part and returns the verdict of the website.
+pub fn submit(year: Option<Year>, day: Day, part: u8, answer: &str) -> Result<Verdict, Error> {
+    if Backend::from_env()? == Backend::AocCli {
+        aoc_cli_check()?;
+        println!("Submitting result via aoc-cli...");
+        let output = aoc_cli::submit(year, day, part, answer)?;
+        let message = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return Verdict::parse(&message).ok_or(Error::UnexpectedResponse(message));
+    }
+
+    println!("Submitting result...");
+    Client::from_env()?.submit(require_year(year)?, day, part, answer)
+}
+
+/// Submits an answer with `submit` until the website checks it. Whenever the website asks to wait,
+/// `wait` is called with the cooldown and should block until it expired, see [`COOLDOWN_MARGIN`].
+pub fn retry_after_cooldown(
+    mut submit: impl FnMut() -> Result<Verdict, Error>,
+    mut wait: impl FnMut(Duration),
+) -> Result<Verdict, Error> {
+    for _ in 0..MAX_COOLDOWNS {
+        match submit()? {
+            Verdict::WaitFor(cooldown) => wait(cooldown),
+            verdict => return Ok(verdict),
+        }
+    }
+
+    submit()
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{retry_after_cooldown, Client, Error, Session};
+    use crate::template::http::mock::Server;
+    use crate::template::submission::Verdict;
+    use crate::{day, year};
+
+    #[test]
+    fn talks_to_the_website() {
+        let server = Server::start(vec![
+            (200, "1abc2\n".into()),
+            (200, include_str!("fixtures/submit/too_low.html").into()),
+            (200, "<main>Internal error</main>".into()),
+            (400, "Puzzle inputs differ by user.".into()),
+        ]);
+        let client = Client::new(&format!("{}/", server.url), Session("abc".into()));
+
+        assert_eq!(client.input(year!(2023), day!(1)).unwrap(), "1abc2\n");
+        assert_eq!(
+            client.submit(year!(2023), day!(1), 2, "54 304").unwrap(),
+            Verdict::TooLow
+        );
+        assert!(matches!(
+            client.submit(year!(2023), day!(1), 2, "54304"),
+            Err(Error::UnexpectedResponse(_))
+        ));
+        assert!(matches!(
+            client.input(year!(2023), day!(2)),
+            Err(Error::Status(400))
+        ));
+
+        let received = server.finish();
+        assert_eq!(received.len(), 4);
+        assert_eq!(received[0].line, "GET /2023/day/1/input HTTP/1.1");
+        assert_eq!(received[0].header("Cookie"), Some("session=abc"));
+        assert_eq!(
+            received[0].header("User-Agent"),
+            Some(concat!("advent_of_code/", env!("CARGO_PKG_VERSION")))
+        );
+        assert_eq!(received[1].line, "POST /2023/day/1/answer HTTP/1.1");
+        assert_eq!(received[1].body, "level=2&answer=54+304");
+    }
+
+    #[test]
+    fn retries_after_cooldown() {
+        let cooldown = |left: &str| {
+            include_str!("fixtures/submit/wait.html")
+                .replace("1m 42s left", &format!("{left} left"))
+        };
+        let server = Server::start(vec![
+            (200, cooldown("42s")),
+            (200, cooldown("3s")),
+            (200, include_str!("fixtures/submit/correct.html").into()),
+        ]);
+        let client = Client::new(&server.url, Session("abc".into()))
+            .with_user_agent("advent_of_code (me@example.com)");
+
+        let mut waits = vec![];
+        let verdict = retry_after_cooldown(
+            || client.submit(year!(2023), day!(1), 1, "42"),
+            |wait| waits.push(wait),
+        );
+
+        assert_eq!(verdict.unwrap(), Verdict::Correct);
+        assert_eq!(waits, [Duration::from_secs(42), Duration::from_secs(3)]);
+        let received = server.finish();
+        assert_eq!(received.len(), 3);
+        assert_eq!(
+            received[2].header("User-Agent"),
+            Some("advent_of_code (me@example.com)")
+        );
+    }
+}
@@ -1,46 +1,290 @@
-use std::io;
+use std::fmt::Display;
+use std::path::Path;
+use std::process::{self, Command};
+use std::{env, io};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
+    baseline::{self, BaselineOptions, Change},
+    binaries, commands,
+    export::{self, Output},
+    paths,
+    readme_benchmarks::Timings,
+    registry::{self, Solution},
+    report::{self, PartReport, Tally},
+    runner::RunOptions,
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{Day, DaySet, Year};
+
+/// Colors of the parts that became faster or slower than their baseline.
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_RED: &str = "\x1b[31m";
+
+/// Options of the `all` command that select the days to run and how their solutions are built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AllOptions {
+    /// [`None`] for the year of the flat layout, see [`crate::template::paths`].
+    pub year: Option<Year>,
+    pub release: bool,
+    pub alloc_stats: bool,
+    /// Run each day's binary, even if the solutions could run in-process.
+    pub subprocess: bool,
+    /// Number of days that run concurrently, unless timed.
+    pub jobs: usize,
+    pub days: DaySet,
+}
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(
+    all_options: AllOptions,
+    options: RunOptions,
+    baseline_options: &BaselineOptions,
+    outputs: &[Output],
+    registry: Option<&[Solution]>,
+) -> Result<(), commands::Error> {
+    let AllOptions {
+        year,
+        release: is_release,
+        alloc_stats,
+        subprocess,
+        jobs,
+        days,
+    } = all_options;
+
+    let is_timed = options.time;
+    // benchmarks of concurrent days would disturb each other.
+    let jobs = if is_timed { 1 } else { jobs };
+    let in_process = can_run_in_process(subprocess, jobs, &options);
+
+    let registry = match registry {
+        Some(registry) => registry,
+        None if in_process => match binaries::build_registry(is_release, alloc_stats) {
+            // the registry binary runs this command again, with the solutions compiled in.
+            Ok(binary) => return run_registry(&binary),
+            Err(e) => {
+                println!("{ANSI_ITALIC}Could not build the registry ({e}), running each day's binary instead.{ANSI_RESET}\n");
+                &[]
+            }
+        },
+        None => &[],
+    };
+    let in_process = in_process && !registry.is_empty();
     let mut timings: Vec<Timings> = vec![];
+    let mut tally = Tally::default();
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
-
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    if is_timed {
+        println!("{ANSI_ITALIC}Benchmark: {}{ANSI_RESET}\n", options.bench);
+    }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    // load the baseline up front, so that a typo does not waste a full benchmark run.
+    let baseline = baseline_options
+        .compare
+        .as_ref()
+        .map(|name| {
+            baseline::load(name).map_err(|e| {
+                commands::Error::Other(format!("failed to load baseline `{name}`: {e}"))
+            })
+        })
+        .transpose()?;
+    let mut regressions = 0;
+
+    let mut record_day = |day: Day, reports: Vec<PartReport>, error: Option<commands::Error>| {
+        let day_tally: Tally = reports.iter().collect();
+        // failures of parts were already reported, only count what the parts do not explain.
+        match error.filter(|_| commands::Error::from_tally(&day_tally).is_none()) {
+            Some(commands::Error::Compile) => {
+                println!("Did not compile.");
+                tally.not_compiled += 1;
+            }
+            Some(e) => {
+                println!("Failed: {e}.");
+                tally.failed += 1;
+            }
+            None if reports.is_empty() => println!("Not solved."),
+            None => {}
+        }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+        if !reports.is_empty() {
+            reports.iter().for_each(|r| tally.add(r));
+            timings.push(child_commands::collect_timings(&reports, day));
         }
-    });
+    };
+
+    if in_process {
+        days.iter().for_each(|day| {
+            print_day_header(day, days);
+            // reports are printed by the runner as soon as each part finishes.
+            let result = registry::find(registry, year, day)
+                .map_or_else(|| Ok(vec![]), |solution| registry::run(solution, options));
+            match result {
+                Ok(reports) => record_day(day, reports, None),
+                Err(e) => record_day(
+                    day,
+                    vec![],
+                    Some(commands::Error::Other(format!("could not read input: {e}"))),
+                ),
+            }
+        });
+    } else {
+        // a day that does not compile is reported in its place, the other days still run.
+        let binaries = binaries::build_all(year, is_release, alloc_stats)?;
+
+        child_commands::run_days(&binaries, days, &options, jobs, |day, run| match run {
+            Some(run) => record_day(day, run.reports, run.error),
+            None if paths::bin_path(year, day).exists() => {
+                record_day(day, vec![], Some(commands::Error::Compile));
+            }
+            None => record_day(day, vec![], None),
+        })?;
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+        if let (Some(baseline), Some(name)) = (&baseline, &baseline_options.compare) {
+            regressions = print_comparison(
+                name,
+                baseline,
+                &timings,
+                &options,
+                baseline_options.threshold,
+            );
+        }
+
+        if let Some(name) = &baseline_options.save {
+            let baseline =
+                baseline::Baseline::from_timings(&timings, options.bench.statistic, options.clock);
+            match baseline::save(name, &baseline) {
+                Ok(()) => println!("Saved baseline `{name}`."),
+                Err(e) => eprintln!("Failed to save baseline `{name}`: {e}"),
+            }
+        }
+
+        let run = export::Run {
+            timings: &timings,
+            total_millis,
+            options,
+            year: paths::effective_year(year),
+            days,
+            metadata: if outputs.iter().any(Output::needs_metadata) {
+                export::Metadata::collect()
+            } else {
+                export::Metadata::default()
+            },
+        };
+
+        for output in outputs {
+            match (output.write(&run), output) {
+                (Ok(()), Output::Readme) => {
+                    println!("Successfully updated README with benchmarks.")
+                }
+                (Ok(()), _) => println!("Wrote timings to {output}."),
+                (Err(e), Output::Readme) => {
+                    eprintln!("Failed to update readme with benchmarks: {e}.");
                 }
+                (Err(e), _) => eprintln!("Failed to write timings to {output}: {e}"),
             }
         }
     }
+
+    println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {tally}");
+
+    if let Some(e) = commands::Error::from_tally(&tally) {
+        return Err(e);
+    }
+
+    if baseline_options.fail_on_regression && regressions > 0 {
+        return Err(commands::Error::Regression);
+    }
+
+    Ok(())
+}
+
+fn print_day_header(day: Day, days: DaySet) {
+    if days.iter().next() != Some(day) {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Whether the days can run in-process, see [`registry`]. Prints why each day's binary is used
+/// instead, unless that was requested with `--subprocess`.
+fn can_run_in_process(subprocess: bool, jobs: usize, options: &RunOptions) -> bool {
+    let reason = if subprocess {
+        return false;
+    } else if options.timeout.is_some() {
+        // it would skew the measurements of every later day.
+        "a part that times out keeps running until its process exits"
+    } else if jobs > 1 {
+        "days only run concurrently in separate processes"
+    } else {
+        return true;
+    };
+
+    println!("{ANSI_ITALIC}Running each day's binary, because {reason}.{ANSI_RESET}\n");
+    false
+}
+
+/// Runs this command in the registry binary, which prints the results itself.
+fn run_registry(binary: &Path) -> Result<(), commands::Error> {
+    let status = Command::new(binary).args(env::args().skip(1)).status()?;
+    match status.code() {
+        Some(0) => Ok(()),
+        // the registry binary already printed its error, only its exit code is passed on.
+        code => process::exit(code.unwrap_or(1)),
+    }
+}
+
+/// Prints how the current run compares to a baseline and returns the number of regressions.
+fn print_comparison(
+    name: &str,
+    baseline: &baseline::Baseline,
+    timings: &[Timings],
+    options: &RunOptions,
+    threshold: f64,
+) -> usize {
+    let statistic = options.bench.statistic;
+    println!(
+        "\n{ANSI_BOLD}Baseline `{name}`{ANSI_RESET} ({} {statistic}, threshold {threshold}%):",
+        options.clock
+    );
+
+    if baseline.statistic != statistic {
+        println!(
+            "{ANSI_ITALIC}note: the baseline was recorded with --stat {}{ANSI_RESET}",
+            baseline.statistic
+        );
+    }
+
+    if baseline.clock != options.clock {
+        println!(
+            "{ANSI_ITALIC}note: the baseline was recorded with --clock {}{ANSI_RESET}",
+            baseline.clock
+        );
+    }
+
+    let comparisons = baseline::compare(baseline, timings, statistic);
+    if comparisons.is_empty() {
+        println!("No parts in common with the baseline.");
+    }
+
+    let mut regressions = 0;
+    for comparison in &comparisons {
+        let line = baseline::format_comparison(comparison, threshold);
+        match comparison.change(threshold) {
+            Change::Unchanged => println!("  {line}"),
+            Change::Improved => println!("{ANSI_GREEN}▼ {line}{ANSI_RESET}"),
+            Change::Regressed => {
+                regressions += 1;
+                println!("{ANSI_BOLD}{ANSI_RED}▲ {line}{ANSI_RESET}");
+            }
+        }
+    }
+
+    regressions
 }
 
 #[derive(Debug)]
@@ -50,144 +294,240 @@ pub enum Error {
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "lost connection to a solution"),
+            Error::Parser(msg) => write!(f, "could not parse solution output: {msg}"),
+            Error::IO(e) => write!(f, "could not run solution: {e}"),
+        }
+    }
+}
+
+impl From<Error> for commands::Error {
+    fn from(e: Error) -> Self {
+        commands::Error::Other(e.to_string())
+    }
+}
+
+impl From<report::Error> for Error {
+    fn from(e: report::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+/// This module encapsulates interaction with these binaries: building them once, invoking them, and collecting the JSON records they emit.
+pub(crate) mod child_commands {
+    use super::{print_day_header, Error};
+    use crate::template::commands;
+    use crate::template::cpu::Clock;
+    use crate::template::input::InputSource;
+    use crate::template::readme_benchmarks::{PartTiming, Timings};
+    use crate::template::report::{PartReport, Status};
+    use crate::template::runner::{print_report, RunOptions};
+    use crate::{Day, DaySet};
     use std::{
+        collections::BTreeMap,
         io::{BufRead, BufReader},
-        path::Path,
+        path::{Path, PathBuf},
         process::{Command, Stdio},
+        sync::atomic::{AtomicUsize, Ordering},
+        sync::mpsc,
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
-
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    /// Output of a solution binary, kept together so that concurrent runs can be printed per day.
+    /// Streamed runs were printed while they ran, and only keep their reports.
+    pub struct DayRun {
+        pub reports: Vec<PartReport>,
+        /// Lines the solution printed itself.
+        pub stdout: Vec<String>,
+        pub stderr: Vec<String>,
+        /// How the binary exited, see [`commands::Error::from_exit_code`].
+        pub error: Option<commands::Error>,
+    }
 
-        if is_release {
-            args.push("--release");
+    impl DayRun {
+        pub fn print(&self) {
+            self.stdout.iter().for_each(|line| println!("{line}"));
+            self.stderr.iter().for_each(|line| eprintln!("{line}"));
+            self.reports.iter().for_each(print_report);
         }
+    }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    /// Runs the solution binary for a day and collects its records and output.
+    /// With `stream`, output is printed as it arrives instead of being kept for [`DayRun::print`].
+    pub fn run_solution(
+        binary: &Path,
+        options: &RunOptions,
+        input: &InputSource,
+        stream: bool,
+    ) -> Result<DayRun, Error> {
+        // mirror runner options to child invocations, requesting machine-readable records.
+        let mut child_args = RunOptions {
+            json: true,
+            ..*options
         }
+        .to_args();
+        child_args.extend(input.to_args());
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        let mut cmd = Command::new(binary)
+            .args(&child_args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
+            .stderr(if stream {
+                Stdio::inherit()
+            } else {
+                Stdio::piped()
+            })
             .spawn()?;
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
-
-        let mut output = vec![];
+        let stdout = BufReader::new(cmd.stdout.take().ok_or(Error::BrokenPipe)?);
+        let stderr = cmd.stderr.take().map(BufReader::new);
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            stderr.map_or_else(Vec::new, |stderr| {
+                stderr.lines().map_while(Result::ok).collect()
+            })
         });
 
+        let mut run = DayRun {
+            reports: vec![],
+            stdout: vec![],
+            stderr: vec![],
+            error: None,
+        };
+
         for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            let line = line?;
+            match PartReport::from_record(&line) {
+                Some(report) => {
+                    let report = report?;
+                    if stream {
+                        print_report(&report);
+                    }
+                    run.reports.push(report);
+                }
+                // anything that is not a record was printed by the solution itself.
+                None if stream => println!("{line}"),
+                None => run.stdout.push(line),
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        run.stderr = thread.join().map_err(|_| Error::BrokenPipe)?;
+        run.error = commands::Error::from_exit_code(cmd.wait()?.code());
 
-        Ok(output)
+        Ok(run)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
+    /// Runs the binaries of the selected days on up to `jobs` threads and prints their output.
+    /// `on_day` is called in order of days, with [`None`] for days without a binary.
+    pub fn run_days(
+        binaries: &[(Day, PathBuf)],
+        selected: DaySet,
+        options: &RunOptions,
+        jobs: usize,
+        mut on_day: impl FnMut(Day, Option<DayRun>),
+    ) -> Result<(), Error> {
+        let days: Vec<Day> = selected.iter().collect();
+
+        let run_day = |day: Day, stream: bool| {
+            binaries
+                .iter()
+                .find(|(d, _)| *d == day)
+                .map(|(_, binary)| run_solution(binary, options, &InputSource::Inputs, stream))
+                .transpose()
+        };
+
+        // a single day at a time prints each part as soon as it finishes.
+        if jobs <= 1 {
+            for day in days {
+                print_day_header(day, selected);
+                on_day(day, run_day(day, true)?);
+            }
+            return Ok(());
+        }
+
+        let next = AtomicUsize::new(0);
+        let (tx, rx) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs {
+                let tx = tx.clone();
+                let (next, days) = (&next, &days);
+                scope.spawn(move || loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    if i >= days.len() || tx.send((i, run_day(days[i], false))).is_err() {
+                        break;
+                    }
+                });
+            }
+            drop(tx);
+
+            // days finish out of order, hold back results until all previous days were printed.
+            let mut finished = BTreeMap::new();
+            let mut next_to_print = 0;
+
+            for (i, run) in rx {
+                finished.insert(i, run);
+                while let Some(run) = finished.remove(&next_to_print) {
+                    let (day, run) = (days[next_to_print], run?);
+                    print_day_header(day, selected);
+                    if let Some(run) = &run {
+                        run.print();
+                    }
+                    on_day(day, run);
+                    next_to_print += 1;
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn collect_timings(reports: &[PartReport], day: Day) -> Timings {
+        let mut timings = Timings {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
         };
 
-        output
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+            .filter(|r| matches!(r.status, Status::Solved | Status::Wrong))
+            .for_each(|r| {
+                // the summary only describes wall time, cpu time is a mean over all samples.
+                let timing = PartTiming {
+                    duration: r.reported_duration(),
+                    samples: r.samples,
+                    stats: r.stats.filter(|_| r.clock == Clock::Wall),
+                    allocations: r.allocations,
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                match r.part {
+                    1 => timings.part_1 = Some(timing),
+                    2 => timings.part_2 = Some(timing),
+                    _ => {}
                 }
 
-                timings.total_nanos += nanos;
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    timings.total_nanos += r.reported_duration().as_nanos() as f64;
+                }
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -200,55 +540,82 @@ mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::collect_timings;
 
         use crate::day;
+        use crate::template::alloc::AllocStats;
+        use crate::template::report::PartReport;
+
+        fn report(part: u8, answer: Option<&str>, nanos: u64, samples: u128) -> PartReport {
+            PartReport::new(
+                day!(1),
+                part,
+                answer.map(Into::into),
+                Duration::from_nanos(nanos),
+                samples,
+            )
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
+            let res = collect_timings(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, Some("0"), 74, 100_000),
+                    report(2, Some("10"), 74_130_000, 99999),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap().duration, Duration::from_nanos(74));
+            assert_eq!(res.part_2.unwrap().samples, 99999);
         }
 
         #[test]
         fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+            let reports = [
+                report(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000, 5),
+                report(2, Some("10s"), 100_000_000, 1),
+            ];
+            let lines: Vec<String> = reports.iter().map(PartReport::to_json).collect();
+            let parsed: Vec<PartReport> = lines
+                .iter()
+                .map(|l| PartReport::from_json(l).unwrap())
+                .collect();
+
+            let res = collect_timings(&parsed, day!(1));
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().duration, Duration::from_secs(2));
+            assert_eq!(res.part_2.unwrap().duration, Duration::from_millis(100));
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
+            let res = collect_timings(&[report(1, None, 10, 1), report(2, None, 10, 1)], day!(1));
+            assert_approx_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+        }
+
+        #[test]
+        fn test_allocations() {
+            let stats = AllocStats {
+                allocations: 2,
+                bytes: 2048,
+                peak_bytes: 1024,
+            };
+            let res = collect_timings(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(1, Some("1"), 10, 1).with_allocations(Some(stats)),
+                    report(2, Some("2"), 10, 1),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.part_1.unwrap().allocations, Some(stats));
+            assert!(res.part_2.unwrap().allocations.is_none());
         }
     }
 }
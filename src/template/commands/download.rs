@@ -1,15 +1,11 @@
-use crate::template::aoc_cli;
-use crate::Day;
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+use crate::template::client;
+use crate::{Day, Year};
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(year: Option<Year>, day: Day) {
+    if let Err(e) = client::download(year, day) {
+        eprintln!("failed to download day {day}: {e}");
         process::exit(1);
     };
 }
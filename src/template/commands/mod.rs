@@ -1,5 +1,123 @@
+use std::fmt::Display;
+use std::io;
+
+use crate::template::report::Tally;
+
 pub mod all;
 pub mod download;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod test;
+
+/// Reasons for `solve` and `all` to fail, each with its own exit code.
+#[derive(Debug)]
+pub enum Error {
+    /// A solution did not compile, cargo prints the details.
+    Compile,
+    /// At least one part panicked or timed out, or a solution failed outside of its parts.
+    Crashed,
+    /// At least one answer does not match its stored answer.
+    WrongAnswer,
+    /// At least one part is slower than its baseline.
+    Regression,
+    /// Anything else, e.g. a missing binary or a broken pipe.
+    Other(String),
+}
+
+impl Error {
+    #[must_use]
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Other(_) => 1,
+            Error::Compile => 2,
+            Error::Crashed => 3,
+            Error::WrongAnswer => 4,
+            Error::Regression => 5,
+        }
+    }
+
+    /// The most severe failure among the parts in `tally`. Unsolved parts are not a failure.
+    #[must_use]
+    pub fn from_tally(tally: &Tally) -> Option<Self> {
+        if tally.not_compiled > 0 {
+            Some(Error::Compile)
+        } else if tally.panicked > 0 || tally.timed_out > 0 || tally.failed > 0 {
+            Some(Error::Crashed)
+        } else if tally.wrong > 0 {
+            Some(Error::WrongAnswer)
+        } else {
+            None
+        }
+    }
+
+    /// Interprets the exit code of a solution binary, see [`Error::exit_code`].
+    #[must_use]
+    pub fn from_exit_code(code: Option<i32>) -> Option<Self> {
+        match code {
+            Some(0) => None,
+            Some(3) => Some(Error::Crashed),
+            Some(4) => Some(Error::WrongAnswer),
+            Some(code) => Some(Error::Other(format!(
+                "solution exited with status code {code}"
+            ))),
+            None => Some(Error::Other("solution was terminated by a signal".into())),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Other(e.to_string())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Compile => write!(f, "failed to compile solutions"),
+            Error::Crashed => write!(f, "a part panicked or timed out"),
+            Error::WrongAnswer => write!(f, "an answer does not match its stored answer"),
+            Error::Regression => write!(f, "a part regressed against the baseline"),
+            Error::Other(msg) => write!(f, "{msg}"),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Error;
+    use crate::template::report::Tally;
+
+    #[test]
+    fn maps_tally_to_error() {
+        let mut tally = Tally {
+            correct: 3,
+            unsolved: 1,
+            ..Tally::default()
+        };
+        assert!(Error::from_tally(&tally).is_none());
+
+        tally.wrong = 1;
+        assert!(matches!(
+            Error::from_tally(&tally),
+            Some(Error::WrongAnswer)
+        ));
+
+        tally.timed_out = 1;
+        assert!(matches!(Error::from_tally(&tally), Some(Error::Crashed)));
+
+        tally.not_compiled = 1;
+        assert!(matches!(Error::from_tally(&tally), Some(Error::Compile)));
+    }
+
+    #[test]
+    fn roundtrips_exit_codes() {
+        for error in [Error::Crashed, Error::WrongAnswer] {
+            let parsed = Error::from_exit_code(Some(error.exit_code())).unwrap();
+            assert_eq!(parsed.exit_code(), error.exit_code());
+        }
+        assert!(Error::from_exit_code(Some(0)).is_none());
+        assert_eq!(Error::from_exit_code(Some(101)).unwrap().exit_code(), 1);
+    }
+}
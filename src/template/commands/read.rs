@@ -1,16 +1,11 @@
 use std::process;
 
-use crate::template::aoc_cli;
-use crate::Day;
+use crate::template::client;
+use crate::{Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+pub fn handle(year: Option<Year>, day: Day, part: Option<u8>) {
+    if let Err(e) = client::read(year, day, part) {
+        eprintln!("failed to read day {day}: {e}");
         process::exit(1);
     };
 }
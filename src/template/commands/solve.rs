@@ -1,31 +1,245 @@
-use std::process::{Command, Stdio};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::time::Duration;
 
-use crate::Day;
+use crate::template::binaries;
+use crate::template::commands::all::child_commands;
+use crate::template::commands::Error;
+use crate::template::input::InputSource;
+use crate::template::paths;
+use crate::template::report::PartReport;
+use crate::template::runner::RunOptions;
+use crate::template::watch::{self, Watcher};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Moves the cursor to the top left and clears the terminal.
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
 
-    if release {
-        cmd_args.push("--release".to_string());
+/// Options of the `solve` command that control how the solution is built and invoked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SolveOptions {
+    /// [`None`] for the year of the flat layout, see [`paths`].
+    pub year: Option<Year>,
+    pub release: bool,
+    pub alloc_stats: bool,
+    pub submit: Option<u8>,
+    /// Resubmit once the cooldown expires if the website asks to wait.
+    pub wait: bool,
+    /// Re-run the solution whenever one of its files changes.
+    pub watch: bool,
+    /// Also run the unit tests of the day when watching.
+    pub tests: bool,
+}
+
+pub fn handle(
+    day: Day,
+    solve_options: SolveOptions,
+    options: RunOptions,
+    input: &InputSource,
+) -> Result<(), Error> {
+    let year = solve_options.year;
+
+    if !paths::bin_path(year, day).exists() {
+        return Err(Error::Other(format!(
+            "{} is not scaffolded yet, run `cargo scaffold {day}{}` first",
+            paths::bin_name(year, day),
+            year.map_or(String::new(), |year| format!(" --year {year}"))
+        )));
+    }
+
+    if let (Some(submit), Some(part)) = (solve_options.submit, options.part) {
+        if submit != part {
+            return Err(Error::Other(format!(
+                "cannot submit part {submit} when only part {part} runs"
+            )));
+        }
+    }
+
+    if solve_options.wait && solve_options.submit.is_none() {
+        return Err(Error::Other("`--wait` requires `--submit`".into()));
+    }
+
+    if solve_options.watch {
+        return watch(day, solve_options, options, input);
     }
 
-    cmd_args.push("--".to_string());
+    // build separately from running, so that compile errors can be told apart from failing parts.
+    let binary = binaries::build_day(year, day, solve_options.release, solve_options.alloc_stats)?;
 
-    if let Some(submit_part) = submit_part {
+    let mut cmd_args = vec![];
+
+    if let Some(submit_part) = solve_options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
+
+        if solve_options.wait {
+            cmd_args.push("--wait".to_string());
+        }
+    }
+
+    cmd_args.extend(options.to_args());
+    cmd_args.extend(input.to_args());
+
+    if options.time && !options.json {
+        println!("{ANSI_ITALIC}Benchmark: {}{ANSI_RESET}", options.bench);
+    }
+
+    let status = if options.json {
+        run_json(&binary, &cmd_args)?
+    } else {
+        Command::new(binary)
+            .args(&cmd_args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()?
+    };
+
+    match Error::from_exit_code(status.code()) {
+        Some(e) => Err(e),
+        None => Ok(()),
     }
+}
 
-    if time {
-        cmd_args.push("--time".to_string());
+/// Runs the solution binary and prints its records as plain JSON lines on stdout, so that they
+/// can be piped into other tools. Everything the solution prints itself goes to stderr.
+fn run_json(binary: &Path, args: &[String]) -> Result<ExitStatus, Error> {
+    let mut cmd = Command::new(binary)
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    let stdout = BufReader::new(
+        cmd.stdout
+            .take()
+            .ok_or_else(|| Error::Other("could not read solution output".into()))?,
+    );
+
+    for line in stdout.lines() {
+        let line = line?;
+        match PartReport::from_record(&line) {
+            Some(Ok(report)) => println!("{}", report.to_json()),
+            Some(Err(e)) => return Err(Error::Other(format!("invalid record: {e}"))),
+            None => eprintln!("{line}"),
+        }
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    Ok(cmd.wait()?)
+}
+
+/// Re-runs the solution every time its binary, the library or its inputs change, until interrupted.
+fn watch(
+    day: Day,
+    solve_options: SolveOptions,
+    options: RunOptions,
+    input: &InputSource,
+) -> Result<(), Error> {
+    if solve_options.submit.is_some() {
+        return Err(Error::Other(
+            "`--submit` cannot be combined with `--watch`".into(),
+        ));
+    }
+
+    if *input == InputSource::Stdin {
+        return Err(Error::Other(
+            "`--stdin` cannot be combined with `--watch`".into(),
+        ));
+    }
+
+    let year = solve_options.year;
+    let mut watcher = Watcher::new(
+        || watch::watched_files(year, day),
+        Duration::from_millis(200),
+    );
+    let mut previous: Vec<PartReport> = vec![];
+
+    loop {
+        print!("{ANSI_CLEAR}");
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET} {ANSI_ITALIC}(watching for changes, press ctrl-c to stop){ANSI_RESET}");
+        println!("------");
+
+        match binaries::build_day(year, day, solve_options.release, solve_options.alloc_stats) {
+            Ok(binary) => {
+                if options.time {
+                    println!("{ANSI_ITALIC}Benchmark: {}{ANSI_RESET}", options.bench);
+                }
+
+                match child_commands::run_solution(&binary, &options, input, true) {
+                    Ok(run) => {
+                        print_changes(&previous, &run.reports);
+                        previous = run.reports;
+                    }
+                    Err(e) => eprintln!("Failed to run solution: {e}"),
+                }
+            }
+            Err(e) => eprintln!("{e}"),
+        }
+
+        if solve_options.tests {
+            println!();
+            run_tests(year, day, options.part);
+        }
+
+        watcher.wait();
+    }
+}
+
+/// Prints the previous answer of each part next to its current one.
+fn print_changes(previous: &[PartReport], current: &[PartReport]) {
+    if previous.is_empty() {
+        return;
+    }
+
+    println!();
+    for report in current {
+        let before = previous.iter().find(|r| r.part == report.part);
+        let (before, after) = (before.map_or("-".into(), describe), describe(report));
+
+        if before == after {
+            println!("{ANSI_ITALIC}Part {}: unchanged{ANSI_RESET}", report.part);
+        } else {
+            println!(
+                "{ANSI_ITALIC}Part {}: {before} -> {ANSI_RESET}{ANSI_BOLD}{after}{ANSI_RESET}",
+                report.part
+            );
+        }
+    }
+}
+
+fn describe(report: &PartReport) -> String {
+    report
+        .answer
+        .clone()
+        .unwrap_or_else(|| format!("({})", report.status))
+}
+
+/// Runs the unit tests of a day, only those of the selected part if there is one.
+fn run_tests(year: Option<Year>, day: Day, part: Option<u8>) {
+    let mut args = vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        paths::bin_name(year, day),
+    ];
+
+    // tests of the scaffolded template are named `test_part_one` and `test_part_two`.
+    match part {
+        Some(1) => args.push("part_one".to_string()),
+        Some(2) => args.push("part_two".to_string()),
+        _ => {}
+    }
+
+    let status = Command::new("cargo")
+        .args(&args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status();
 
-    cmd.wait().unwrap();
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(_) => println!("{ANSI_BOLD}Tests failed.{ANSI_RESET}"),
+        Err(e) => eprintln!("Failed to run tests: {e}"),
+    }
 }
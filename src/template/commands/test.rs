@@ -0,0 +1,247 @@
+use std::path::Path;
+use std::time::Duration;
+use std::{fs, io};
+
+use crate::template::commands::all::child_commands;
+use crate::template::commands::Error;
+use crate::template::input::InputSource;
+use crate::template::report::{PartReport, Status, Tally};
+use crate::template::runner::RunOptions;
+use crate::template::{answers, binaries, paths, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, Year};
+
+/// Examples usually finish instantly, so a part that runs this long is most likely stuck on an
+/// example that was meant for the other part.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs a day's solution against each of its example files and compares the answers to the
+/// expected answers stored next to them, see [`answers::get_example_path`].
+pub fn handle(
+    year: Option<Year>,
+    day: Day,
+    release: bool,
+    timeout: Option<Duration>,
+) -> Result<(), Error> {
+    if !paths::bin_path(year, day).exists() {
+        return Err(Error::Other(format!(
+            "{} is not scaffolded yet, run `cargo scaffold {day}{}` first",
+            paths::bin_name(year, day),
+            year.map_or(String::new(), |year| format!(" --year {year}"))
+        )));
+    }
+
+    let examples_dir = paths::data_dir(year).join("examples");
+    let examples = find_examples(&examples_dir, day)?;
+    if examples.is_empty() {
+        return Err(Error::Other(format!(
+            "no examples found for day {day} in {}",
+            examples_dir.display()
+        )));
+    }
+
+    let binary = binaries::build_day(year, day, release, false)?;
+    let options = RunOptions {
+        timeout: timeout.or(Some(DEFAULT_TIMEOUT)),
+        ..RunOptions::default()
+    };
+
+    let mut rows = vec![];
+    let mut errors = vec![];
+    let mut tally = Tally::default();
+
+    for name in examples {
+        let expected = answers::read_example(year, day, name.as_deref())
+            .map_err(|e| Error::Other(e.to_string()))?;
+        let has_expected = expected.part_1.is_some() || expected.part_2.is_some();
+        // examples of a single part are named after it, e.g. `08-2.txt`.
+        let example_part: Option<u8> = name.as_deref().and_then(|n| n.parse().ok());
+        let source = InputSource::Example(name.clone());
+        let example = match &name {
+            Some(name) => format!("{day}-{name}.txt"),
+            None => format!("{day}.txt"),
+        };
+
+        // skip parts that are not tested, they might not even finish on this example.
+        let part = match (&expected.part_1, &expected.part_2) {
+            (Some(_), None) => Some(1),
+            (None, Some(_)) => Some(2),
+            (None, None) => example_part,
+            (Some(_), Some(_)) => None,
+        };
+        let options = RunOptions { part, ..options };
+        let run = child_commands::run_solution(&binary, &options, &source, false)?;
+
+        // an example with stored answers only tests the parts it has an answer for.
+        for report in run.reports {
+            let expected = expected.get(report.part).map(ToString::to_string);
+            let is_tested = if has_expected {
+                expected.is_some()
+            } else {
+                example_part.is_none_or(|part| part == report.part)
+            };
+
+            if !is_tested {
+                continue;
+            }
+
+            let report = report.with_expected(expected);
+            tally.add(&report);
+
+            if let Some(error) = &report.error {
+                errors.push(format!("{example} part {}: {error}", report.part));
+            }
+            rows.push(Row::new(&example, &report));
+        }
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+    format_table(&rows)
+        .iter()
+        .for_each(|line| println!("{line}"));
+    errors.iter().for_each(|error| println!("\n{error}"));
+    println!("\n{ANSI_BOLD}Summary:{ANSI_RESET} {tally}");
+
+    match Error::from_tally(&tally) {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Finds the examples of a day: [`None`] for `DD.txt`, and the name of each `DD-<name>.txt`.
+fn find_examples(dir: &Path, day: Day) -> io::Result<Vec<Option<String>>> {
+    let mut examples: Vec<Option<String>> = fs::read_dir(dir)?
+        .filter_map(|entry| parse_example_name(day, &entry.ok()?.file_name().to_string_lossy()))
+        .collect();
+    examples.sort();
+    Ok(examples)
+}
+
+fn parse_example_name(day: Day, file_name: &str) -> Option<Option<String>> {
+    let stem = file_name
+        .strip_suffix(".txt")?
+        .strip_prefix(&day.to_string())?;
+    if stem.is_empty() {
+        Some(None)
+    } else {
+        stem.strip_prefix('-')
+            .filter(|name| !name.is_empty())
+            .map(|name| Some(name.to_string()))
+    }
+}
+
+/// One line of the results table.
+struct Row {
+    example: String,
+    part: String,
+    expected: String,
+    actual: String,
+    time: String,
+    verdict: &'static str,
+}
+
+impl Row {
+    fn new(example: &str, report: &PartReport) -> Self {
+        let finished = matches!(report.status, Status::Solved | Status::Wrong);
+
+        Self {
+            example: example.to_string(),
+            part: report.part.to_string(),
+            expected: report.expected.clone().unwrap_or_else(|| "-".into()),
+            actual: match (&report.answer, report.status) {
+                (Some(answer), _) => answer.clone(),
+                (None, status) => status.to_string(),
+            },
+            time: if finished {
+                format!("{:.1?}", report.duration)
+            } else {
+                "-".into()
+            },
+            verdict: match report.status {
+                Status::Solved if report.is_verified() => "✔",
+                Status::Solved | Status::Unsolved => "",
+                Status::Wrong | Status::Panicked | Status::TimedOut => "✘",
+            },
+        }
+    }
+
+    fn cells(&self) -> [&str; 6] {
+        [
+            &self.example,
+            &self.part,
+            &self.expected,
+            &self.actual,
+            &self.time,
+            self.verdict,
+        ]
+    }
+}
+
+fn format_table(rows: &[Row]) -> Vec<String> {
+    let header = ["Example", "Part", "Expected", "Actual", "Time", ""];
+
+    let mut widths = header.map(|cell| cell.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row.cells()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let format_line = |cells: [&str; 6]| {
+        let line: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        line.join("  ").trim_end().to_string()
+    };
+
+    let mut lines = vec![format!("{ANSI_BOLD}{}{ANSI_RESET}", format_line(header))];
+    lines.extend(rows.iter().map(|row| format_line(row.cells())));
+    lines
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_table, parse_example_name, Row};
+    use crate::day;
+    use crate::template::report::PartReport;
+
+    #[test]
+    fn parses_example_names() {
+        assert_eq!(parse_example_name(day!(8), "08.txt"), Some(None));
+        assert_eq!(
+            parse_example_name(day!(8), "08-2.txt"),
+            Some(Some("2".into()))
+        );
+        assert_eq!(parse_example_name(day!(8), "08.toml"), None);
+        assert_eq!(parse_example_name(day!(8), "08-.txt"), None);
+        assert_eq!(parse_example_name(day!(8), "10.txt"), None);
+        assert_eq!(parse_example_name(day!(1), "10.txt"), None);
+    }
+
+    #[test]
+    fn formats_table() {
+        let report = |answer: &str, expected: &str| {
+            PartReport::new(
+                day!(8),
+                1,
+                Some(answer.into()),
+                Duration::from_micros(12),
+                1,
+            )
+            .with_expected(Some(expected.into()))
+        };
+
+        let rows = [
+            Row::new("08.txt", &report("2", "2")),
+            Row::new("08-b.txt", &report("7", "6")),
+        ];
+        let lines = format_table(&rows);
+
+        assert_eq!(lines[1], "08.txt    1     2         2       12.0µs  ✔");
+        assert_eq!(lines[2], "08-b.txt  1     6         7       12.0µs  ✘");
+    }
+}
@@ -0,0 +1,212 @@
+/// CPU time measurements for solution parts.
+/// On Linux, user and system time of the whole process are read with `getrusage`, so time spent
+/// in threads spawned by a solution is included. Other platforms report no CPU time.
+use std::fmt::Display;
+use std::ops::Sub;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+/// The clock that represents a part in reports, the readme and baselines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Clock {
+    /// Elapsed real time, measured with [`std::time::Instant`].
+    #[default]
+    Wall,
+    /// User and system CPU time combined.
+    Cpu,
+}
+
+impl Clock {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Clock::Wall => "wall",
+            Clock::Cpu => "cpu",
+        }
+    }
+}
+
+impl Display for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Clock {
+    type Err = ClockFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wall" => Ok(Clock::Wall),
+            "cpu" => Ok(Clock::Cpu),
+            _ => Err(ClockFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Clock`].
+#[derive(Debug)]
+pub struct ClockFromStrError;
+
+impl std::error::Error for ClockFromStrError {}
+
+impl Display for ClockFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of: wall, cpu")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTime {
+    pub user: Duration,
+    pub system: Duration,
+}
+
+impl CpuTime {
+    #[must_use]
+    pub fn total(&self) -> Duration {
+        self.user + self.system
+    }
+
+    /// Divides the time evenly over `runs`, e.g. to get the time of a single benchmark sample.
+    #[must_use]
+    pub fn per_run(&self, runs: u128) -> Self {
+        let runs = u32::try_from(runs).unwrap_or(u32::MAX).max(1);
+        Self {
+            user: self.user / runs,
+            system: self.system / runs,
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        json!({"user_nanos": self.user.as_nanos(), "system_nanos": self.system.as_nanos()})
+    }
+
+    #[must_use]
+    pub fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            user: Duration::from_nanos(value.get("user_nanos")?.as_u64()?),
+            system: Duration::from_nanos(value.get("system_nanos")?.as_u64()?),
+        })
+    }
+}
+
+impl Sub for CpuTime {
+    type Output = CpuTime;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            user: self.user.saturating_sub(rhs.user),
+            system: self.system.saturating_sub(rhs.system),
+        }
+    }
+}
+
+/// Reads the CPU time consumed by this process so far, or [`None`] if it is not available.
+#[must_use]
+pub fn now() -> Option<CpuTime> {
+    sys::now()
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::mem::MaybeUninit;
+    use std::os::raw::{c_int, c_long};
+    use std::time::Duration;
+
+    use super::CpuTime;
+
+    const RUSAGE_SELF: c_int = 0;
+
+    #[repr(C)]
+    struct Timeval {
+        tv_sec: c_long,
+        tv_usec: c_long,
+    }
+
+    /// Mirrors `struct rusage`; only the first two fields are read.
+    #[repr(C)]
+    struct Rusage {
+        ru_utime: Timeval,
+        ru_stime: Timeval,
+        _rest: [c_long; 14],
+    }
+
+    extern "C" {
+        fn getrusage(who: c_int, usage: *mut Rusage) -> c_int;
+    }
+
+    fn to_duration(tv: &Timeval) -> Duration {
+        let secs = u64::try_from(tv.tv_sec).unwrap_or(0);
+        let micros = u32::try_from(tv.tv_usec).unwrap_or(0);
+        Duration::from_secs(secs) + Duration::from_micros(u64::from(micros))
+    }
+
+    pub fn now() -> Option<CpuTime> {
+        let mut usage = MaybeUninit::<Rusage>::zeroed();
+
+        // SAFETY: `usage` points to writable memory with the layout of `struct rusage`.
+        let usage = unsafe {
+            if getrusage(RUSAGE_SELF, usage.as_mut_ptr()) != 0 {
+                return None;
+            }
+            usage.assume_init()
+        };
+
+        Some(CpuTime {
+            user: to_duration(&usage.ru_utime),
+            system: to_duration(&usage.ru_stime),
+        })
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use super::CpuTime;
+
+    pub fn now() -> Option<CpuTime> {
+        None
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Clock, CpuTime};
+
+    #[test]
+    fn parses_clock() {
+        assert_eq!("wall".parse::<Clock>().unwrap(), Clock::Wall);
+        assert_eq!("cpu".parse::<Clock>().unwrap(), Clock::Cpu);
+        assert!("user".parse::<Clock>().is_err());
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let cpu = CpuTime {
+            user: Duration::from_micros(30),
+            system: Duration::from_micros(4),
+        };
+        assert_eq!(CpuTime::from_json(&cpu.to_json()), Some(cpu));
+        assert_eq!(cpu.total(), Duration::from_micros(34));
+        assert_eq!(cpu.per_run(2).user, Duration::from_micros(15));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn measures_cpu_time() {
+        let before = super::now().unwrap();
+        let mut x = 0_u64;
+        for i in 0..5_000_000 {
+            x = std::hint::black_box(x.wrapping_mul(31).wrapping_add(i));
+        }
+        let spent = super::now().unwrap() - before;
+        assert!(spent.total() > Duration::ZERO);
+    }
+}
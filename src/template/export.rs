@@ -0,0 +1,405 @@
+/// Module that writes the timings of a `cargo time` run to one or more outputs:
+/// the benchmark table in the readme, or a JSON or CSV file for charting in other tools.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, io, thread};
+
+use serde_json::{json, Value};
+
+use crate::template::readme_benchmarks::{self, PartTiming, Timings};
+use crate::template::runner::RunOptions;
+use crate::template::stats::Summary;
+use crate::{DaySet, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for Error {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        match e {
+            readme_benchmarks::Error::Parser(msg) | readme_benchmarks::Error::Mismatch(msg) => {
+                Error::Parser(msg)
+            }
+            readme_benchmarks::Error::IO(e) => Error::IO(e),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "{msg}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// A destination for the timings of a run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Output {
+    /// The benchmark table in `README.md`.
+    Readme,
+    /// A JSON document with all statistics and the metadata of the run.
+    Json(PathBuf),
+    /// A CSV file with one row per part, repeating the metadata of the run on every row.
+    Csv(PathBuf),
+}
+
+impl Output {
+    /// Whether the output includes the [`Metadata`] of the run.
+    #[must_use]
+    pub fn needs_metadata(&self) -> bool {
+        !matches!(self, Output::Readme)
+    }
+
+    pub fn write(&self, run: &Run) -> Result<(), Error> {
+        match self {
+            Output::Readme => Ok(readme_benchmarks::update(
+                run.timings,
+                run.total_millis,
+                run.options.bench.statistic,
+                run.options.clock,
+                run.days,
+            )?),
+            Output::Json(path) => Ok(fs::write(path, to_json(run) + "\n")?),
+            Output::Csv(path) => Ok(fs::write(path, to_csv(run))?),
+        }
+    }
+}
+
+impl Display for Output {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Output::Readme => f.write_str("README.md"),
+            Output::Json(path) | Output::Csv(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+impl FromStr for Output {
+    type Err = OutputFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "readme" {
+            return Ok(Output::Readme);
+        }
+
+        let path = PathBuf::from(s);
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Ok(Output::Json(path)),
+            Some("csv") => Ok(Output::Csv(path)),
+            _ => Err(OutputFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`Output`].
+#[derive(Debug)]
+pub struct OutputFromStrError;
+
+impl std::error::Error for OutputFromStrError {}
+
+impl Display for OutputFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting `readme` or a path ending in .json or .csv")
+    }
+}
+
+/// The machine and revision that a run was measured on.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    /// Hash of the checked out commit, if the run happened in a git repository.
+    pub commit: Option<String>,
+    /// Whether tracked files had uncommitted changes.
+    pub dirty: bool,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub os: String,
+    pub arch: String,
+    /// The model name of the cpu, if it can be determined.
+    pub cpu: Option<String>,
+    /// Number of logical cpus.
+    pub cores: usize,
+}
+
+impl Metadata {
+    #[must_use]
+    pub fn collect() -> Self {
+        let git = |args: &[&str]| {
+            let output = Command::new("git").args(args).output().ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+
+        Self {
+            commit: git(&["rev-parse", "HEAD"]),
+            dirty: git(&["status", "--porcelain", "--untracked-files=no"])
+                .is_some_and(|status| !status.is_empty()),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().map_or(1, Into::into),
+        }
+    }
+}
+
+/// Reads the cpu model from `/proc/cpuinfo`, which only exists on Linux.
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .find(|line| line.starts_with("model name"))
+        .and_then(|line| line.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+}
+
+/// Everything an output needs to know about a finished run.
+pub struct Run<'a> {
+    pub timings: &'a [Timings],
+    pub total_millis: f64,
+    pub options: RunOptions,
+    /// The year of the solutions, if it is known.
+    pub year: Option<Year>,
+    /// The days that were selected for this run.
+    pub days: DaySet,
+    pub metadata: Metadata,
+}
+
+impl Run<'_> {
+    fn parts(&self) -> impl Iterator<Item = (&Timings, u8, &PartTiming)> {
+        self.timings
+            .iter()
+            .flat_map(|t| (1..=2).filter_map(move |part| Some((t, part, t.part(part)?))))
+    }
+}
+
+#[must_use]
+pub fn to_json(run: &Run) -> String {
+    let metadata = &run.metadata;
+    let bench = &run.options.bench;
+
+    let parts: Vec<Value> = run
+        .parts()
+        .map(|(t, part, timing)| {
+            json!({
+                "day": t.day.into_inner(),
+                "part": part,
+                "duration_nanos": timing.duration.as_nanos(),
+                "samples": timing.samples,
+                "stats": timing.stats.as_ref().map(Summary::to_json),
+                "allocations": timing.allocations.as_ref().map(|a| a.to_json()),
+            })
+        })
+        .collect();
+
+    json!({
+        "commit": metadata.commit,
+        "dirty": metadata.dirty,
+        "timestamp": metadata.timestamp,
+        "year": run.year.map(Year::into_inner),
+        "host": {
+            "os": metadata.os,
+            "arch": metadata.arch,
+            "cpu": metadata.cpu,
+            "cores": metadata.cores,
+        },
+        "bench": {
+            "statistic": bench.statistic.as_str(),
+            "clock": run.options.clock.as_str(),
+            "reject_outliers": bench.reject_outliers,
+            "budget_nanos": bench.budget.as_nanos(),
+            "min_samples": bench.min_samples,
+            "max_samples": bench.max_samples,
+            "warmup": bench.warmup,
+        },
+        "total_millis": run.total_millis,
+        "parts": parts,
+    })
+    .to_string()
+}
+
+const CSV_HEADER: &str = "commit,dirty,timestamp,os,arch,cpu,cores,statistic,clock,year,day,part,duration_nanos,samples,mean_nanos,median_nanos,min_nanos,max_nanos,stddev_nanos,p95_nanos,p99_nanos,outliers,allocations,allocated_bytes,peak_bytes";
+
+#[must_use]
+pub fn to_csv(run: &Run) -> String {
+    let metadata = &run.metadata;
+    let nanos = |d: Duration| d.as_nanos().to_string();
+
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+
+    for (t, part, timing) in run.parts() {
+        let mut cells = vec![
+            metadata.commit.clone().unwrap_or_default(),
+            metadata.dirty.to_string(),
+            metadata.timestamp.to_string(),
+            metadata.os.clone(),
+            metadata.arch.clone(),
+            metadata.cpu.clone().unwrap_or_default(),
+            metadata.cores.to_string(),
+            run.options.bench.statistic.to_string(),
+            run.options.clock.to_string(),
+            run.year.map(|year| year.to_string()).unwrap_or_default(),
+            t.day.into_inner().to_string(),
+            part.to_string(),
+            nanos(timing.duration),
+            timing.samples.to_string(),
+        ];
+
+        match &timing.stats {
+            Some(s) => cells.extend(
+                [s.mean, s.median, s.min, s.max, s.stddev, s.p95, s.p99]
+                    .map(nanos)
+                    .into_iter()
+                    .chain([s.outliers.to_string()]),
+            ),
+            None => cells.extend([""; 8].map(String::from)),
+        }
+
+        match &timing.allocations {
+            Some(a) => cells.extend([a.allocations, a.bytes, a.peak_bytes].map(|n| n.to_string())),
+            None => cells.extend([""; 3].map(String::from)),
+        }
+
+        let row: Vec<String> = cells.iter().map(|cell| escape_csv(cell)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quotes a cell if it contains a separator, a quote or a line break.
+fn escape_csv(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use serde_json::Value;
+
+    use super::{to_csv, to_json, Metadata, Output, Run};
+    use crate::template::readme_benchmarks::{PartTiming, Timings};
+    use crate::template::runner::RunOptions;
+    use crate::template::stats::Summary;
+    use crate::{day, year, DaySet};
+
+    fn timings() -> Vec<Timings> {
+        let samples = [Duration::from_nanos(70), Duration::from_nanos(90)];
+        vec![Timings {
+            day: day!(8),
+            part_1: Some(PartTiming {
+                duration: Duration::from_nanos(100),
+                samples: 1,
+                stats: None,
+                allocations: None,
+            }),
+            part_2: Some(PartTiming {
+                duration: Duration::from_nanos(80),
+                samples: 2,
+                stats: Some(Summary::from_samples(&samples, false)),
+                allocations: None,
+            }),
+            total_nanos: 180.0,
+        }]
+    }
+
+    fn run(timings: &[Timings]) -> Run<'_> {
+        Run {
+            timings,
+            total_millis: 0.00018,
+            options: RunOptions::default(),
+            year: Some(year!(2023)),
+            days: DaySet::all(),
+            metadata: Metadata {
+                commit: Some("b492200".into()),
+                dirty: false,
+                timestamp: 1_701_993_600,
+                os: "linux".into(),
+                arch: "x86_64".into(),
+                cpu: Some("AMD Ryzen 7, 8 cores".into()),
+                cores: 16,
+            },
+        }
+    }
+
+    #[test]
+    fn parses_outputs() {
+        assert_eq!("readme".parse::<Output>().unwrap(), Output::Readme);
+        assert_eq!(
+            "out/timings.json".parse::<Output>().unwrap(),
+            Output::Json(PathBuf::from("out/timings.json"))
+        );
+        assert_eq!(
+            "timings.csv".parse::<Output>().unwrap(),
+            Output::Csv(PathBuf::from("timings.csv"))
+        );
+        assert!("timings.txt".parse::<Output>().is_err());
+    }
+
+    #[test]
+    fn writes_json() {
+        let timings = timings();
+        let value = serde_json::from_str::<Value>(&to_json(&run(&timings))).unwrap();
+
+        assert_eq!(value.get("commit").unwrap().as_str(), Some("b492200"));
+        assert_eq!(value.get("year").unwrap().as_u64(), Some(2023));
+        assert_eq!(
+            value.get("host").unwrap().get("cores").unwrap().as_u64(),
+            Some(16)
+        );
+
+        let parts = value.get("parts").unwrap().as_array().unwrap();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].get("stats").unwrap().is_null());
+        assert_eq!(
+            parts[1]
+                .get("stats")
+                .unwrap()
+                .get("median_nanos")
+                .unwrap()
+                .as_u64(),
+            Some(80)
+        );
+    }
+
+    #[test]
+    fn writes_csv() {
+        let timings = timings();
+        let csv = to_csv(&run(&timings));
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "b492200,false,1701993600,linux,x86_64,\"AMD Ryzen 7, 8 cores\",16,mean,wall,2023,8,1,100,1,,,,,,,,,,,"
+        );
+        assert!(lines[2].starts_with(
+            "b492200,false,1701993600,linux,x86_64,\"AMD Ryzen 7, 8 cores\",16,mean,wall,2023,8,2,80,2,80,80,70,90,10,"
+        ));
+    }
+}
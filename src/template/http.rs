@@ -0,0 +1,243 @@
+/// Minimal HTTP client for talking to the Advent of Code website, built on [`ureq`].
+///
+/// Requests go over `https` with rustls, or over plain `http`, e.g. to a local server that stands
+/// in for the website in tests.
+use std::fmt::Display;
+use std::io;
+use std::time::Duration;
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Request {
+    pub method: Method,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// A form encoded body, see [`encode_form`].
+    pub body: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Method::Get => f.write_str("GET"),
+            Method::Post => f.write_str("POST"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Request {
+    #[must_use]
+    pub fn get(url: &str) -> Self {
+        Self {
+            method: Method::Get,
+            url: url.to_string(),
+            headers: vec![],
+            body: None,
+        }
+    }
+
+    #[must_use]
+    pub fn post(url: &str, body: String) -> Self {
+        Self {
+            method: Method::Post,
+            url: url.to_string(),
+            headers: vec![(
+                "Content-Type".into(),
+                "application/x-www-form-urlencoded".into(),
+            )],
+            body: Some(body),
+        }
+    }
+
+    #[must_use]
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sends the request. Error statuses are returned as a [`Response`] as well,
+    /// only requests that did not get a response fail.
+    pub fn send(&self) -> io::Result<Response> {
+        let agent = ureq::AgentBuilder::new().timeout(TIMEOUT).build();
+
+        let mut request = agent.request(&self.method.to_string(), &self.url);
+        for (name, value) in &self.headers {
+            request = request.set(name, value);
+        }
+
+        let result = match &self.body {
+            Some(body) => request.send_string(body),
+            None => request.call(),
+        };
+
+        let response = match result {
+            Ok(response) | Err(ureq::Error::Status(_, response)) => response,
+            Err(ureq::Error::Transport(e)) => return Err(io::Error::other(e.to_string())),
+        };
+
+        Ok(Response {
+            status: response.status(),
+            body: response.into_string()?,
+        })
+    }
+}
+
+/// Encodes key-value pairs as `application/x-www-form-urlencoded`.
+#[must_use]
+pub fn encode_form(pairs: &[(&str, &str)]) -> String {
+    let encode = |s: &str| {
+        s.bytes()
+            .map(|b| match b {
+                b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                    (b as char).to_string()
+                }
+                b' ' => "+".to_string(),
+                _ => format!("%{b:02X}"),
+            })
+            .collect::<String>()
+    };
+
+    pairs
+        .iter()
+        .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+/// A local server that stands in for the website in tests.
+#[cfg(all(test, feature = "test_lib"))]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    /// A request as received by the [`Server`].
+    #[derive(Debug)]
+    pub struct Received {
+        /// The request line, e.g. `GET /2023/day/8/input HTTP/1.1`.
+        pub line: String,
+        pub headers: Vec<String>,
+        pub body: String,
+    }
+
+    impl Received {
+        pub fn header(&self, name: &str) -> Option<&str> {
+            self.headers.iter().find_map(|header| {
+                let (key, value) = header.split_once(':')?;
+                key.eq_ignore_ascii_case(name).then_some(value.trim())
+            })
+        }
+    }
+
+    pub struct Server {
+        pub url: String,
+        handle: JoinHandle<Vec<Received>>,
+    }
+
+    impl Server {
+        /// Answers one request with each of `responses`, given as status and body.
+        pub fn start(responses: Vec<(u16, String)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+
+            let handle = thread::spawn(move || {
+                let mut received = vec![];
+                for (status, response) in responses {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                    let mut lines = vec![];
+                    loop {
+                        let mut line = String::new();
+                        reader.read_line(&mut line).unwrap();
+                        let line = line.trim_end().to_string();
+                        if line.is_empty() {
+                            break;
+                        }
+                        lines.push(line);
+                    }
+
+                    let mut request = Received {
+                        line: lines.remove(0),
+                        headers: lines,
+                        body: String::new(),
+                    };
+                    let length = request
+                        .header("Content-Length")
+                        .map_or(0, |length| length.parse().unwrap());
+                    let mut body = vec![0; length];
+                    reader.read_exact(&mut body).unwrap();
+                    request.body = String::from_utf8(body).unwrap();
+                    received.push(request);
+
+                    write!(
+                        stream,
+                        "HTTP/1.0 {status} Mock\r\nContent-Length: {}\r\n\r\n{response}",
+                        response.len()
+                    )
+                    .unwrap();
+                }
+                received
+            });
+
+            Self { url, handle }
+        }
+
+        /// Waits for all responses to be sent and returns the requests that were received.
+        pub fn finish(self) -> Vec<Received> {
+            self.handle.join().unwrap()
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::mock::Server;
+    use super::{encode_form, Request};
+
+    #[test]
+    fn sends_requests() {
+        let server = Server::start(vec![(200, "ok".into()), (404, "not found".into())]);
+
+        let response = Request::post(
+            &format!("{}/2023/day/8/answer", server.url),
+            encode_form(&[("level", "1"), ("answer", "42")]),
+        )
+        .header("Cookie", "session=abc")
+        .send()
+        .unwrap();
+        assert_eq!((response.status, response.body.as_str()), (200, "ok"));
+
+        let response = Request::get(&format!("{}/2023/day/26", server.url))
+            .send()
+            .unwrap();
+        assert_eq!(response.status, 404);
+
+        let received = server.finish();
+        assert_eq!(received[0].line, "POST /2023/day/8/answer HTTP/1.1");
+        assert_eq!(received[0].header("cookie"), Some("session=abc"));
+        assert_eq!(received[0].body, "level=1&answer=42");
+        assert_eq!(received[1].line, "GET /2023/day/26 HTTP/1.1");
+    }
+
+    #[test]
+    fn encodes_forms() {
+        assert_eq!(
+            encode_form(&[("answer", "a b&c=d/ü")]),
+            "answer=a+b%26c%3Dd%2F%C3%BC"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+/// Selects where a solution binary reads its puzzle input from.
+use std::io::{self, Read};
+use std::path::PathBuf;
+use std::{env, fs};
+
+use crate::template::paths;
+use crate::{Day, Year};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    #[default]
+    Inputs,
+    /// An explicit file, passed with `--input <path>`.
+    File(PathBuf),
+    /// Standard input, selected with `--stdin`.
+    Stdin,
+    /// An example file in `data/examples`, selected with `--example [name]`.
+    /// A name selects `DD-<name>.txt` instead of `DD.txt`, see [`InputSource::for_part`].
+    Example(Option<String>),
+}
+
+impl InputSource {
+    /// Reads the input source from the arguments passed to a solution binary.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::from_args(&args)
+    }
+
+    fn from_args(args: &[String]) -> Self {
+        let value_after = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+                .filter(|x| !x.starts_with("--"))
+        };
+
+        if let Some(path) = value_after("--input") {
+            Self::File(path.into())
+        } else if args.iter().any(|x| x == "--stdin") {
+            Self::Stdin
+        } else if args.iter().any(|x| x == "--example") {
+            Self::Example(value_after("--example").cloned())
+        } else {
+            Self::Inputs
+        }
+    }
+
+    /// Converts the input source into arguments for a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Inputs => vec![],
+            Self::File(path) => vec!["--input".into(), path.display().to_string()],
+            Self::Stdin => vec!["--stdin".into()],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(name)) => vec!["--example".into(), name.clone()],
+        }
+    }
+
+    /// The source that one part reads. The examples of a day can be split by part, e.g.
+    /// `DD-1.txt` and `DD-2.txt`, which take the place of `DD.txt` like in [`read_file_part`].
+    ///
+    /// [`read_file_part`]: crate::template::read_file_part
+    #[must_use]
+    pub fn for_part(&self, year: Option<Year>, day: Day, part: u8) -> Self {
+        let name = part.to_string();
+        match self {
+            Self::Example(None) if example_path(year, day, Some(&name)).exists() => {
+                Self::Example(Some(name))
+            }
+            _ => self.clone(),
+        }
+    }
+
+    /// Reads the input for `day` of `year` from this source, see [`paths::data_dir`].
+    pub fn read(&self, year: Option<Year>, day: Day) -> io::Result<String> {
+        match self {
+            Self::Inputs => fs::read_to_string(paths::input_path(year, day)),
+            Self::File(path) => fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            Self::Example(name) => fs::read_to_string(example_path(year, day, name.as_deref())),
+        }
+    }
+}
+
+fn example_path(year: Option<Year>, day: Day, name: Option<&str>) -> PathBuf {
+    let file_name = match name {
+        Some(name) => format!("{day}-{name}.txt"),
+        None => format!("{day}.txt"),
+    };
+    paths::data_dir(year).join("examples").join(file_name)
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::InputSource;
+    use crate::day;
+
+    fn parse(args: &[&str]) -> InputSource {
+        let args: Vec<String> = args.iter().map(|x| (*x).to_string()).collect();
+        InputSource::from_args(&args)
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse(&["08", "--time"]), InputSource::Inputs);
+        assert_eq!(
+            parse(&["08", "--input", "stress.txt"]),
+            InputSource::File("stress.txt".into())
+        );
+        assert_eq!(parse(&["08", "--stdin", "--time"]), InputSource::Stdin);
+        assert_eq!(parse(&["08", "--example"]), InputSource::Example(None));
+        assert_eq!(
+            parse(&["08", "--example", "2", "--json"]),
+            InputSource::Example(Some("2".into()))
+        );
+        assert_eq!(
+            parse(&["08", "--example", "--json"]),
+            InputSource::Example(None)
+        );
+    }
+
+    #[test]
+    fn roundtrip_args() {
+        for source in [
+            InputSource::Inputs,
+            InputSource::File("a.txt".into()),
+            InputSource::Stdin,
+            InputSource::Example(None),
+            InputSource::Example(Some("b".into())),
+        ] {
+            let mut args = vec!["08".to_string()];
+            args.extend(source.to_args());
+            assert_eq!(InputSource::from_args(&args), source);
+        }
+    }
+
+    #[test]
+    fn selects_examples_of_parts() {
+        let example = InputSource::Example(None);
+        assert_eq!(
+            example.for_part(None, day!(8), 2),
+            InputSource::Example(Some("2".into()))
+        );
+        assert_eq!(example.for_part(None, day!(2), 2), example);
+        assert_eq!(
+            InputSource::Inputs.for_part(None, day!(8), 2),
+            InputSource::Inputs
+        );
+    }
+}
@@ -0,0 +1,310 @@
+/// Renders puzzle descriptions in the terminal, from the markdown written by [`puzzle::to_markdown`].
+///
+/// Only the markdown that the conversion produces is understood: headings, emphasis, code,
+/// code blocks, lists and links, of which the text is shown.
+///
+/// [`puzzle::to_markdown`]: crate::template::puzzle::to_markdown
+use std::env;
+use std::mem;
+use std::process::{Command, Stdio};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const DEFAULT_WIDTH: usize = 80;
+
+/// Code blocks are indented instead of fenced, and never wrapped.
+const CODE_INDENT: &str = "    ";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    heading: bool,
+    emphasis: bool,
+    code: bool,
+}
+
+impl Style {
+    fn apply(self, text: &str) -> String {
+        if self == Style::default() {
+            return text.to_string();
+        }
+
+        let mut styled = String::new();
+        if self.heading || self.emphasis {
+            styled.push_str(ANSI_BOLD);
+        }
+        if self.code {
+            styled.push_str(ANSI_ITALIC);
+        }
+        styled.push_str(text);
+        styled.push_str(ANSI_RESET);
+        styled
+    }
+}
+
+/// A word with the styles of its characters, e.g. `*42*,` is an emphasized and a plain piece.
+type Word = Vec<(String, Style)>;
+
+/// The width of the terminal, from `COLUMNS` or `stty`, 80 columns if neither knows it.
+#[must_use]
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .or_else(|| {
+            // `stty` reads the size of the terminal connected to its stdin.
+            let output = Command::new("stty")
+                .arg("size")
+                .stdin(Stdio::inherit())
+                .stderr(Stdio::null())
+                .output()
+                .ok()?;
+            let size = String::from_utf8_lossy(&output.stdout).to_string();
+            size.split_whitespace().nth(1)?.parse().ok()
+        })
+        .filter(|&width| width > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Renders markdown with ANSI styles, wrapping text at `width` columns.
+#[must_use]
+pub fn render(markdown: &str, width: usize) -> String {
+    blocks(markdown)
+        .iter()
+        .map(|block| render_block(block, width))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Splits markdown into blocks separated by blank lines. Code blocks are a block of their own.
+fn blocks(markdown: &str) -> Vec<Vec<&str>> {
+    let mut blocks = vec![];
+    let mut block = vec![];
+    let mut fenced = false;
+
+    for line in markdown.lines() {
+        let fence = line.trim_start().starts_with("```");
+        if fence && !fenced && !block.is_empty() {
+            blocks.push(mem::take(&mut block));
+        }
+
+        if fenced || fence {
+            block.push(line);
+            if fence {
+                fenced = !fenced;
+                if !fenced {
+                    blocks.push(mem::take(&mut block));
+                }
+            }
+        } else if line.trim().is_empty() {
+            if !block.is_empty() {
+                blocks.push(mem::take(&mut block));
+            }
+        } else {
+            block.push(line);
+        }
+    }
+
+    if !block.is_empty() {
+        blocks.push(block);
+    }
+    blocks
+}
+
+fn render_block(lines: &[&str], width: usize) -> String {
+    let first = lines[0].trim_start();
+
+    if first.starts_with("```") {
+        return lines[1..]
+            .iter()
+            .filter(|line| !line.trim_start().starts_with("```"))
+            .map(|line| format!("{CODE_INDENT}{line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    if first.starts_with('#') {
+        let heading = Style {
+            heading: true,
+            ..Style::default()
+        };
+        let text = lines.join(" ");
+        return wrap(
+            &words(text.trim_start_matches('#').trim(), heading),
+            width,
+            "",
+        );
+    }
+
+    if first.starts_with("- ") {
+        // list items continue on the following lines until the next item starts.
+        let mut items: Vec<String> = vec![];
+        for line in lines.iter().map(|line| line.trim()) {
+            match (line.strip_prefix("- "), items.last_mut()) {
+                (Some(item), _) => items.push(item.to_string()),
+                (None, Some(item)) => {
+                    item.push(' ');
+                    item.push_str(line);
+                }
+                (None, None) => items.push(line.to_string()),
+            }
+        }
+
+        return items
+            .iter()
+            .map(|item| {
+                format!(
+                    "- {}",
+                    wrap(
+                        &words(item, Style::default()),
+                        width.saturating_sub(2),
+                        "  "
+                    )
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+    }
+
+    wrap(&words(&lines.join(" "), Style::default()), width, "")
+}
+
+/// Parses inline markdown into words, dropping the markup.
+fn words(text: &str, base: Style) -> Vec<Word> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = vec![];
+    let mut word: Word = vec![];
+    let mut style = base;
+    // the positions of `](` and `)` of the link that is open.
+    let mut link: Option<(usize, usize)> = None;
+
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '`' => style.code = !style.code,
+            '*' if !style.code => style.emphasis = !style.emphasis,
+            '\\' if !style.code && i + 1 < chars.len() => {
+                i += 1;
+                push_char(&mut word, chars[i], style);
+            }
+            '[' if !style.code && link.is_none() => match link_end(&chars, i) {
+                Some(end) => link = Some(end),
+                None => push_char(&mut word, c, style),
+            },
+            ']' if link.is_some_and(|(close, _)| close == i) => {
+                let (_, end) = link.take().unwrap_or_default();
+                i = end;
+            }
+            ' ' => {
+                if !word.is_empty() {
+                    words.push(mem::take(&mut word));
+                }
+            }
+            _ => push_char(&mut word, c, style),
+        }
+        i += 1;
+    }
+
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn push_char(word: &mut Word, c: char, style: Style) {
+    push_str(word, c.encode_utf8(&mut [0; 4]), style);
+}
+
+fn push_str(word: &mut Word, s: &str, style: Style) {
+    match word.last_mut() {
+        Some((text, last)) if *last == style => text.push_str(s),
+        _ => word.push((s.to_string(), style)),
+    }
+}
+
+/// Returns the positions of `](` and `)` if a link starts at `start`, e.g. `[text](/2023/day/1)`.
+fn link_end(chars: &[char], start: usize) -> Option<(usize, usize)> {
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            ']' => break,
+            _ => {}
+        }
+        i += 1;
+    }
+
+    if chars.get(i + 1) != Some(&'(') {
+        return None;
+    }
+    let end = chars[i + 2..].iter().position(|&c| c == ')')? + i + 2;
+    Some((i, end))
+}
+
+/// Fills lines of at most `width` columns with words, a word longer than that gets a line of its own.
+/// Lines after the first one start with `indent`.
+fn wrap(words: &[Word], width: usize, indent: &str) -> String {
+    let mut lines: Vec<Word> = vec![];
+    let mut line: Word = vec![];
+    let mut line_width = 0;
+
+    for word in words {
+        let word_width: usize = word.iter().map(|(text, _)| text.chars().count()).sum();
+
+        if line_width > 0 && line_width + 1 + word_width > width {
+            lines.push(mem::take(&mut line));
+            line_width = 0;
+        }
+        if line_width > 0 {
+            // the space joins the styles of both words if they match.
+            let style = match (line.last(), word.first()) {
+                (Some((_, left)), Some((_, right))) if left == right => *left,
+                _ => Style::default(),
+            };
+            push_str(&mut line, " ", style);
+            line_width += 1;
+        }
+
+        for (text, style) in word {
+            push_str(&mut line, text, *style);
+        }
+        line_width += word_width;
+    }
+
+    lines.push(line);
+    lines
+        .iter()
+        .map(|line| {
+            line.iter()
+                .map(|(text, style)| style.apply(text))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(&format!("\n{indent}"))
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::render;
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = "## --- Day 1: Trebuchet?! ---\n\nSomething is *wrong* with [global snow](/2015/day/1) production, \\*really\\*.\n\n```\n1abc2\npqr3stu8vwx\n```\n\n- Adding these together produces *`142`*.";
+
+        assert_eq!(
+            render(markdown, 24),
+            format!(
+                "{ANSI_BOLD}--- Day 1: Trebuchet?!{ANSI_RESET}\n\
+                 {ANSI_BOLD}---{ANSI_RESET}\n\n\
+                 Something is {ANSI_BOLD}wrong{ANSI_RESET} with\n\
+                 global snow production,\n\
+                 *really*.\n\n    \
+                 1abc2\n    \
+                 pqr3stu8vwx\n\n\
+                 - Adding these together\n  \
+                 produces {ANSI_BOLD}{ANSI_ITALIC}142{ANSI_RESET}."
+            )
+        );
+    }
+}
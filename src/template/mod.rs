@@ -1,36 +1,139 @@
-use crate::Day;
+use crate::{Day, Year};
 use std::{env, fs};
 
+pub mod alloc;
+pub mod answers;
 pub mod aoc_cli;
+pub mod baseline;
+pub mod binaries;
+pub mod cli;
+pub mod client;
 pub mod commands;
+pub mod cpu;
+pub mod export;
+pub mod http;
+pub mod input;
+pub mod markdown;
+pub mod paths;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod registry;
+pub mod report;
 pub mod runner;
+pub mod spans;
+pub mod stats;
+pub mod submission;
+pub mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// `folder` is relative to the data directory of `year`, e.g. `examples` reads from
+/// `data/examples` for the year of the flat layout and from `data/2022/examples` for 2022, see
+/// [`paths`].
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, year: Option<Year>, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(paths::data_dir(year))
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Reads the file of one part, e.g. `data/examples/08-2.txt`.
+/// Falls back to [`read_file`] for days whose parts share a single file.
+#[must_use]
+pub fn read_file_part(folder: &str, year: Option<Year>, day: Day, part: u8) -> String {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd
+        .join(paths::data_dir(year))
+        .join(folder)
+        .join(format!("{day}-{part}.txt"));
+
+    if filepath.exists() {
+        let f = fs::read_to_string(filepath);
+        f.expect("could not open input file")
+    } else {
+        read_file(folder, year, day)
+    }
+}
+
+/// Reads a named example of a year, e.g. `data/examples/10-b.txt` for `read_example(YEAR, DAY, "b")`.
+#[must_use]
+pub fn read_example(year: Option<Year>, day: Day, name: &str) -> String {
+    let f = input::InputSource::Example(Some(name.to_string())).read(year, day);
+    f.expect("could not open example file")
+}
+
+/// Reads the inputs of both parts selected by the arguments passed to a solution binary, see
+/// [`input::InputSource`]. The parts share their input, unless the day's examples are split by part.
+///
+/// The inputs are leaked so that parts can run on threads that might outlive `main`.
+#[must_use]
+pub fn read_inputs(year: Option<Year>, day: Day) -> [&'static str; 2] {
+    let source = input::InputSource::from_env();
+    let read = |source: &input::InputSource| -> &'static str {
+        let f = source.read(year, day);
+        Box::leak(f.expect("could not open input file").into_boxed_str())
+    };
+
+    let (one, two) = (source.for_part(year, day, 1), source.for_part(year, day, 2));
+    let input = read(&one);
+    [input, if two == one { input } else { read(&two) }]
+}
+
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
+/// Solutions of the year of the flat layout pass only the day, e.g. `solution!(8)`, solutions of other
+/// years pass the year as well, e.g. `solution!(2022, 8)`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@impl $year:expr, $day:expr) => {
+        /// The current year, [`None`] for the year of the flat layout, see `AOC_FLAT_YEAR`.
+        const YEAR: Option<advent_of_code::Year> = $year;
+
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        // unused when the solution is compiled into the `registry` binary.
+        #[allow(dead_code)]
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let [input_one, input_two] = advent_of_code::template::read_inputs(YEAR, DAY);
+            exit_with_status(&[
+                run_part(part_one, input_one, YEAR, DAY, 1),
+                run_part(part_two, input_two, YEAR, DAY, 2),
+            ]);
         }
+
+        /// Entry point for the in-process runner of the `all` command.
+        #[allow(dead_code)]
+        pub const SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution {
+                year: YEAR,
+                day: DAY,
+                run: |input, options| {
+                    use advent_of_code::template::input::InputSource;
+                    use advent_of_code::template::runner::execute_part;
+                    let source = &InputSource::Inputs;
+                    let mut reports = vec![];
+                    if options.runs_part(1) {
+                        reports.push(execute_part(part_one, input, YEAR, DAY, 1, options, source).1);
+                    }
+                    if options.runs_part(2) {
+                        reports.push(execute_part(part_two, input, YEAR, DAY, 2, options, source).1);
+                    }
+                    reports
+                },
+            };
+    };
+    ($day:expr) => {
+        advent_of_code::solution!(@impl None, $day);
+    };
+    ($year:expr, $day:expr) => {
+        advent_of_code::solution!(@impl Some(advent_of_code::year!($year)), $day);
     };
 }
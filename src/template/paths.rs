@@ -0,0 +1,106 @@
+/// Module that decides where the files of a year live.
+///
+/// The year pinned with `AOC_FLAT_YEAR` uses the flat layout, e.g. `data/inputs/08.txt` and
+/// `src/bin/08.rs`. Other years are nested, e.g. `data/2022/inputs/08.txt` and `src/bin/2022-08.rs`.
+/// Throughout the template, a year of [`None`] stands for the year of the flat layout, see
+/// [`Year::flat_from_env`]. Pinning it separately from `AOC_YEAR` keeps existing solutions in
+/// their year when `AOC_YEAR` moves on to a new one.
+use std::path::PathBuf;
+
+use crate::{Day, Year};
+
+/// Maps a year selected with `--year`, or `AOC_YEAR` if none was selected, to the year that
+/// determines the layout: [`None`] for the year of the flat layout.
+#[must_use]
+pub fn resolve(year: Option<Year>) -> Option<Year> {
+    year.or_else(Year::from_env)
+        .filter(|year| Some(*year) != Year::flat_from_env())
+}
+
+/// The year to report to Advent of Code, e.g. for downloads and submissions.
+#[must_use]
+pub fn effective_year(year: Option<Year>) -> Option<Year> {
+    year.or_else(Year::flat_from_env)
+}
+
+/// The directory that contains the inputs, examples, puzzles and answers of a year.
+#[must_use]
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    match year {
+        Some(year) => PathBuf::from("data").join(year.to_string()),
+        None => PathBuf::from("data"),
+    }
+}
+
+/// The name of the solution binary of a day, e.g. `08` or `2022-08`.
+#[must_use]
+pub fn bin_name(year: Option<Year>, day: Day) -> String {
+    match year {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
+#[must_use]
+pub fn bin_path(year: Option<Year>, day: Day) -> PathBuf {
+    PathBuf::from("src")
+        .join("bin")
+        .join(format!("{}.rs", bin_name(year, day)))
+}
+
+#[must_use]
+pub fn input_path(year: Option<Year>, day: Day) -> PathBuf {
+    data_dir(year).join("inputs").join(format!("{day}.txt"))
+}
+
+/// The puzzle description of a day, as downloaded by `cargo download`.
+#[must_use]
+pub fn puzzle_path(year: Option<Year>, day: Day) -> PathBuf {
+    data_dir(year).join("puzzles").join(format!("{day}.md"))
+}
+
+/// Parses the name of a solution binary, see [`bin_name`].
+#[must_use]
+pub fn parse_bin_name(name: &str) -> Option<(Option<Year>, Day)> {
+    match name.split_once('-') {
+        Some((year, day)) => Some((Some(year.parse().ok()?), parse_day(day)?)),
+        None => Some((None, parse_day(name)?)),
+    }
+}
+
+/// Parses the two digit day of a binary name, e.g. `08`.
+fn parse_day(s: &str) -> Option<Day> {
+    (s.len() == 2).then_some(())?;
+    s.parse().ok()
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{bin_name, bin_path, data_dir, parse_bin_name};
+    use crate::{day, year};
+
+    #[test]
+    fn builds_paths() {
+        assert_eq!(data_dir(None), PathBuf::from("data"));
+        assert_eq!(data_dir(Some(year!(2022))), PathBuf::from("data/2022"));
+        assert_eq!(bin_name(None, day!(8)), "08");
+        assert_eq!(
+            bin_path(Some(year!(2022)), day!(8)),
+            PathBuf::from("src/bin/2022-08.rs")
+        );
+    }
+
+    #[test]
+    fn parses_bin_names() {
+        assert_eq!(parse_bin_name("08"), Some((None, day!(8))));
+        assert_eq!(
+            parse_bin_name("2022-25"),
+            Some((Some(year!(2022)), day!(25)))
+        );
+        assert_eq!(parse_bin_name("8"), None);
+        assert_eq!(parse_bin_name("2022-26"), None);
+        assert_eq!(parse_bin_name("advent_of_code"), None);
+    }
+}
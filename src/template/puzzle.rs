@@ -0,0 +1,327 @@
+/// Returns the contents of each `<article>` element of a page, in order.
+/// The description of a puzzle has one article per unlocked part, a submission response has one.
+#[must_use]
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(length) = rest[content_start..].find("</article>") else {
+            break;
+        };
+
+        articles.push(&rest[content_start..content_start + length]);
+        rest = &rest[content_start + length..];
+    }
+
+    articles
+}
+
+/// The heading that starts the second part of a puzzle description, see [`to_markdown`].
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+
+/// A piece of html: text, or an opening or closing tag given by its name and its full contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Text(&'a str),
+    Open(&'a str, &'a str),
+    Close(&'a str),
+}
+
+fn tokens(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+
+        let Some(length) = rest[start..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = &rest[start + 1..start + length];
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        tokens.push(if tag.starts_with('/') {
+            Token::Close(name)
+        } else {
+            Token::Open(name, tag)
+        });
+        rest = &rest[start + length + 1..];
+    }
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+
+    tokens
+}
+
+/// Returns the value of an attribute of a tag, e.g. the `href` of `a href="/2023/day/1"`.
+fn attribute<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let start = tag.find(&format!(" {name}=\""))? + name.len() + 3;
+    let length = tag[start..].find('"')?;
+    Some(&tag[start..start + length])
+}
+
+/// Converts the html of an article to plain text, with paragraphs separated by blank lines.
+#[must_use]
+pub fn to_text(html: &str) -> String {
+    let mut text = String::new();
+
+    for token in tokens(html) {
+        match token {
+            Token::Text(s) => text.push_str(&decode_entities(s)),
+            Token::Close("p" | "pre" | "h2" | "ul") => text.push_str("\n\n"),
+            Token::Open("li", _) => text.push_str("- "),
+            Token::Close("li") | Token::Open("br", _) => text.push('\n'),
+            _ => {}
+        }
+    }
+
+    // collapse the blank lines that nested blocks leave behind.
+    let mut lines: Vec<&str> = vec![];
+    for line in text.lines().map(str::trim_end) {
+        if !(line.is_empty() && lines.last().is_none_or(|last| last.is_empty())) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Converts the html of an article to markdown: headings, emphasis, code, code blocks for the
+/// examples, lists and links. Emphasized code, which the website uses for answers, becomes ``*`42`*``.
+#[must_use]
+pub fn to_markdown(html: &str) -> String {
+    let tokens = tokens(html);
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut in_code = false;
+    let mut emphasized_code = false;
+    let mut link = None;
+
+    for (i, token) in tokens.iter().enumerate() {
+        match *token {
+            Token::Text(s) if in_pre || in_code => markdown.push_str(&decode_entities(s)),
+            Token::Text(s) => markdown.push_str(&escape(&decode_entities(s).replace('\n', " "))),
+            Token::Open("h2", _) => markdown.push_str("\n## "),
+            Token::Close("h2" | "p" | "ul") => markdown.push_str("\n\n"),
+            Token::Open("li", _) => markdown.push_str("\n- "),
+            Token::Open("pre", _) => {
+                markdown.push_str("\n```\n");
+                in_pre = true;
+            }
+            Token::Close("pre") => {
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+                in_pre = false;
+            }
+            _ if in_pre => {}
+            Token::Open("code", _) => {
+                in_code = true;
+                emphasized_code = is_emphasized_code(&tokens[i..]);
+                markdown.push_str(if emphasized_code { "*`" } else { "`" });
+            }
+            Token::Close("code") => {
+                in_code = false;
+                if !emphasized_code {
+                    markdown.push('`');
+                }
+                emphasized_code = false;
+            }
+            Token::Close("em") if emphasized_code => markdown.push_str("`*"),
+            // emphasis within code can not be expressed in markdown, and is dropped.
+            Token::Open("em", _) | Token::Close("em") if in_code => {}
+            Token::Open("em", _) | Token::Close("em") => markdown.push('*'),
+            Token::Open("a", tag) => {
+                link = attribute(tag, "href");
+                if link.is_some() {
+                    markdown.push('[');
+                }
+            }
+            Token::Close("a") => {
+                if let Some(href) = link.take() {
+                    markdown.push_str(&format!("]({href})"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // html whitespace between blocks leaves blank and indented lines behind, code blocks are kept as is.
+    let mut lines: Vec<&str> = vec![];
+    let mut fenced = false;
+    for line in markdown.lines() {
+        if fenced {
+            fenced = line != "```";
+            lines.push(line);
+            continue;
+        }
+
+        let line = line.trim();
+        fenced = line == "```";
+        if !(line.is_empty() && lines.last().is_none_or(|last| last.is_empty())) {
+            lines.push(line);
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Whether the code that starts `tokens` is emphasized as a whole, e.g. `<code><em>42</em></code>`.
+fn is_emphasized_code(tokens: &[Token]) -> bool {
+    let Some(end) = tokens
+        .iter()
+        .position(|token| *token == Token::Close("code"))
+    else {
+        return false;
+    };
+    let emphasis = tokens[..end]
+        .iter()
+        .filter(|token| matches!(token, Token::Open("em", _)))
+        .count();
+    emphasis == 1
+        && matches!(tokens[1], Token::Open("em", _))
+        && tokens[end - 1] == Token::Close("em")
+}
+
+/// Escapes the characters that would otherwise be read as markdown.
+fn escape(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '`' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Returns the section of a puzzle description in markdown that describes `part`,
+/// or [`None`] if the part is not unlocked yet.
+#[must_use]
+pub fn part(markdown: &str, part: u8) -> Option<&str> {
+    let part_two = markdown.find(PART_TWO_HEADING);
+    match (part, part_two) {
+        (1, Some(start)) => Some(markdown[..start].trim_end()),
+        (1, None) => Some(markdown),
+        (2, Some(start)) => Some(&markdown[start..]),
+        _ => None,
+    }
+}
+
+/// Decodes the named entities that appear on the website, and numeric character references.
+fn decode_entities(s: &str) -> String {
+    let mut decoded = String::new();
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let character = entity.and_then(|(name, _)| match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => {
+                let code = match name.strip_prefix("#x").or(name.strip_prefix("#X")) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => name.strip_prefix('#')?.parse().ok(),
+                };
+                char::from_u32(code?)
+            }
+        });
+
+        match (character, entity) {
+            (Some(character), Some((_, end))) => {
+                decoded.push(character);
+                rest = &rest[end + 1..];
+            }
+            _ => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{articles, part, to_markdown, to_text};
+
+    #[test]
+    fn extracts_article_text() {
+        let html = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with global snow production.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul>
+<li>Both parts &amp; both stars.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>54304</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Digits like &quot;one&quot; &lt;count&gt;&#39;s too.</p></article>
+</main>"#;
+
+        let articles = articles(html);
+        assert_eq!(articles.len(), 2);
+        assert_eq!(
+            to_text(articles[0]),
+            "--- Day 1: Trebuchet?! ---\n\nSomething is wrong with global snow production.\n\n1abc2\npqr3stu8vwx\n\n- Both parts & both stars."
+        );
+        assert_eq!(
+            to_text(articles[1]),
+            "--- Part Two ---\n\nDigits like \"one\" <count>'s too."
+        );
+    }
+
+    #[test]
+    fn converts_to_markdown() {
+        let html = r#"<h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href="/2015" target="_blank">global snow</a> production, *really*.</p>
+<pre><code>1<em>abc</em>2
+pqr3stu8vwx
+</code></pre>
+<ul>
+<li>Adding <code>12</code> and <code>38</code> produces <code><em>50</em></code>.</li>
+<li>Entities like <code>&lt;<em>a</em>&gt;</code> &amp; stars.</li>
+</ul>
+"#;
+
+        assert_eq!(
+            to_markdown(html),
+            "## --- Day 1: Trebuchet?! ---\n\nSomething is *wrong* with [global snow](/2015) production, \\*really\\*.\n\n```\n1abc2\npqr3stu8vwx\n```\n\n- Adding `12` and `38` produces *`50`*.\n- Entities like `<a>` & stars."
+        );
+    }
+
+    #[test]
+    fn selects_parts() {
+        let markdown = "## --- Day 1: Trebuchet?! ---\n\nOne.\n\n## --- Part Two ---\n\nTwo.";
+        assert_eq!(
+            part(markdown, 1),
+            Some("## --- Day 1: Trebuchet?! ---\n\nOne.")
+        );
+        assert_eq!(part(markdown, 2), Some("## --- Part Two ---\n\nTwo."));
+
+        let markdown = "## --- Day 1: Trebuchet?! ---\n\nOne.";
+        assert_eq!(part(markdown, 1), Some(markdown));
+        assert_eq!(part(markdown, 2), None);
+    }
+}
@@ -1,14 +1,21 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::time::Duration;
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::alloc::{format_alloc_stats, AllocStats};
+use crate::template::cpu::Clock;
+use crate::template::stats::{Statistic, Summary};
+use crate::{Day, DaySet};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
+    /// The existing table measures another statistic or clock than the run that would be merged
+    /// into it.
+    Mismatch(String),
     IO(io::Error),
 }
 
@@ -18,14 +25,35 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[derive(Clone)]
+/// The benchmark result of a single part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartTiming {
+    /// The duration measured by the selected clock and statistic, or the duration of a single run.
+    pub duration: Duration,
+    pub samples: u128,
+    pub stats: Option<Summary>,
+    pub allocations: Option<AllocStats>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    #[must_use]
+    pub fn part(&self, part: u8) -> Option<&PartTiming> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -58,112 +86,341 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
+/// A row of the benchmarks table.
+#[derive(Debug, Clone, PartialEq)]
+struct Row {
+    day: Day,
+    part_1: String,
+    part_2: String,
+    /// The contents of the allocations cell, if any part was run with allocation accounting.
+    allocations: Option<String>,
+    millis: f64,
+}
+
+impl From<&Timings> for Row {
+    fn from(timing: &Timings) -> Self {
+        let format_time = |p: &Option<PartTiming>| {
+            p.as_ref()
+                .map_or_else(|| "-".into(), |p| format!("{:.1?}", p.duration))
+        };
+
+        let format_allocs = |p: &Option<PartTiming>| {
+            p.as_ref()
+                .and_then(|p| p.allocations.as_ref())
+                .map_or_else(|| "-".into(), format_alloc_stats)
+        };
+
+        let has_allocs = [&timing.part_1, &timing.part_2]
+            .iter()
+            .any(|p| p.as_ref().is_some_and(|p| p.allocations.is_some()));
+
+        Self {
+            day: timing.day,
+            part_1: format_time(&timing.part_1),
+            part_2: format_time(&timing.part_2),
+            allocations: has_allocs.then(|| {
+                format!(
+                    "`{}` / `{}`",
+                    format_allocs(&timing.part_1),
+                    format_allocs(&timing.part_2)
+                )
+            }),
+            millis: timing.total_nanos / 1_000_000_f64,
+        }
+    }
+}
+
+/// Reads the rows of an existing benchmarks table, so that rows of days that were not run can be kept.
+fn parse_rows(table: &str) -> Vec<Row> {
+    table
+        .lines()
+        .filter(|line| line.starts_with("| [Day "))
+        .filter_map(|line| {
+            let cells: Vec<&str> = line.split('|').map(str::trim).collect();
+
+            let day = cells.get(1)?.strip_prefix("[Day ")?.split(']').next()?;
+            let day = day.parse().ok()?;
+            let part_1 = cells.get(2)?.trim_matches('`').to_string();
+            let part_2 = cells.get(3)?.trim_matches('`').to_string();
+
+            let allocations = cells
+                .get(4)
+                .filter(|cell| !cell.is_empty() && **cell != "`-` / `-`")
+                .map(ToString::to_string);
+
+            let millis = [&part_1, &part_2]
+                .iter()
+                .filter_map(|time| parse_duration(time))
+                .map(|d| d.as_secs_f64() * 1000.0)
+                .sum();
+
+            Some(Row {
+                day,
+                part_1,
+                part_2,
+                allocations,
+                millis,
+            })
+        })
+        .collect()
+}
+
+/// Parses a duration in the format of its `Debug` implementation, e.g. `74.1ms`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let split = s.find(|c: char| c.is_alphabetic())?;
+    let (value, unit) = s.split_at(split);
+    let value: f64 = value.parse().ok()?;
+
+    let secs = match unit {
+        "ns" => value / 1e9,
+        "µs" => value / 1e6,
+        "ms" => value / 1e3,
+        "s" => value,
+        _ => return None,
+    };
+
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// Describes what the durations of a table measure, printed after its total, e.g. ` (median)`.
+fn total_label(statistic: Statistic, clock: Clock) -> String {
+    match (clock, statistic) {
+        (Clock::Wall, Statistic::Mean) => String::new(),
+        (Clock::Wall, _) => format!(" ({statistic})"),
+        (Clock::Cpu, _) => " (cpu time)".into(),
+    }
+}
+
+/// Reads the label of an existing benchmarks table, see [`total_label`].
+fn parse_total_label(table: &str) -> Option<&str> {
+    table
+        .lines()
+        .find_map(|line| line.strip_prefix("**Total: "))?
+        .split_once("**")
+        .map(|(_, label)| label)
+}
+
+fn construct_table(
+    prefix: &str,
+    rows: Vec<Row>,
+    total_millis: f64,
+    statistic: Statistic,
+    clock: Clock,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+    // the allocations column is only shown if any solution was run with allocation accounting.
+    let has_allocs = rows.iter().any(|r| r.allocations.is_some());
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_allocs {
+        lines.push("| Day | Part 1 | Part 2 | Allocations |".into());
+        lines.push("| :---: | :---: | :---:  | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
+
+    for row in rows {
+        let path = get_path_for_bin(row.day);
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
+            row.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            row.part_1,
+            row.part_2
+        );
+
+        if has_allocs {
+            let allocations = row.allocations.unwrap_or_else(|| "`-` / `-`".into());
+            line.push_str(&format!(" {allocations} |"));
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(format!(
+        "**Total: {total_millis:.2}ms**{}",
+        total_label(statistic, clock)
+    ));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Replaces the benchmarks table. If only some `days` were run, the rows of other days are kept
+/// and count towards the total.
+fn update_content(
+    s: &mut String,
+    timings: &[Timings],
+    total_millis: f64,
+    statistic: Statistic,
+    clock: Clock,
+    days: DaySet,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+
+    let mut rows: Vec<Row> = timings.iter().map(Row::from).collect();
+    let mut total_millis = total_millis;
+
+    if !days.is_all() {
+        let table = &s[positions.pos_start..positions.pos_end];
+        let kept: Vec<Row> = parse_rows(table)
+            .into_iter()
+            .filter(|row| !days.contains(row.day))
+            .collect();
+
+        // rows measured with another statistic or clock can not be compared with this run.
+        let label = total_label(statistic, clock);
+        let existing = parse_total_label(table).unwrap_or_default();
+        if !kept.is_empty() && existing != label {
+            let describe = |label: &str| match label.trim().trim_matches(['(', ')']) {
+                "" => "mean".to_string(),
+                label => label.to_string(),
+            };
+            return Err(Error::Mismatch(format!(
+                "the existing table measures `{}`, this run measures `{}`. Run all days to replace it",
+                describe(existing),
+                describe(&label)
+            )));
+        }
+
+        for row in kept {
+            total_millis += row.millis;
+            rows.push(row);
+        }
+
+        rows.sort_by_key(|row| row.day);
+    }
+
+    let table = construct_table("##", rows, total_millis, statistic, clock);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+pub fn update(
+    timings: &[Timings],
+    total_millis: f64,
+    statistic: Statistic,
+    clock: Clock,
+    days: DaySet,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, statistic, clock, days)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use std::time::Duration;
+
+    use super::{parse_duration, update_content, Error, PartTiming, Timings, MARKER};
+    use crate::template::alloc::AllocStats;
+    use crate::template::cpu::Clock;
+    use crate::template::stats::Statistic;
+    use crate::{day, DaySet};
+
+    fn timing(millis: u64) -> Option<PartTiming> {
+        Some(PartTiming {
+            duration: Duration::from_millis(millis),
+            samples: 1,
+            stats: None,
+            allocations: None,
+        })
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                part_1: timing(10),
+                part_2: timing(20),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                part_1: timing(30),
+                part_2: timing(40),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                part_1: timing(40),
+                part_2: timing(50),
                 total_nanos: 9e+10,
             },
         ]
     }
 
+    /// A run whose timings are written into the table, a timed run of all days by default.
+    struct Run {
+        timings: Vec<Timings>,
+        total_millis: f64,
+        statistic: Statistic,
+        clock: Clock,
+        days: DaySet,
+    }
+
+    impl Default for Run {
+        fn default() -> Self {
+            Self {
+                timings: get_mock_timings(),
+                total_millis: 190.0,
+                statistic: Statistic::Mean,
+                clock: Clock::Wall,
+                days: DaySet::all(),
+            }
+        }
+    }
+
+    fn render(readme: &str, run: Run) -> Result<String, Error> {
+        let mut s = readme.to_string();
+        update_content(
+            &mut s,
+            &run.timings,
+            run.total_millis,
+            run.statistic,
+            run.clock,
+            run.days,
+        )?;
+        Ok(s)
+    }
+
     #[test]
-    #[should_panic]
     fn errors_if_marker_not_present() {
-        let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        assert!(render("# readme", Run::default()).is_err());
     }
 
     #[test]
-    #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let readme = format!("{} {} {}", MARKER, MARKER, MARKER);
+        assert!(render(&readme, Run::default()).is_err());
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let readme = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let s = render(&readme, Run::default()).unwrap();
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let readme = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
+        let s = render(&readme, Run::default()).unwrap();
+        let s = render(&s, Run::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let readme = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let s = render(&readme, Run::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -172,9 +429,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +440,108 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_allocations() {
+        let mut timings = get_mock_timings();
+        timings[0].part_1.as_mut().unwrap().allocations = Some(AllocStats {
+            allocations: 2,
+            bytes: 2048,
+            peak_bytes: 1024,
+        });
+        let readme = format!("{}\n{}", MARKER, MARKER);
+        let s = render(
+            &readme,
+            Run {
+                timings,
+                ..Run::default()
+            },
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Allocations |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `2 allocs · 2.0 KiB · 1.0 KiB peak` / `-` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_cpu_time() {
+        let readme = format!("{}\n{}", MARKER, MARKER);
+        let run = Run {
+            statistic: Statistic::Median,
+            clock: Clock::Cpu,
+            ..Run::default()
+        };
+        let s = render(&readme, run).unwrap();
+        assert!(s.contains("**Total: 190.00ms** (cpu time)"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_statistic() {
+        let readme = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let run = Run {
+            statistic: Statistic::Median,
+            ..Run::default()
+        };
+        let s = render(&readme, run).unwrap();
+        assert!(s.contains("**Total: 190.00ms** (median)"));
+    }
+
+    #[test]
+    fn merges_selected_days() {
+        let readme = format!("{}\n{}", MARKER, MARKER);
+        let s = render(&readme, Run::default()).unwrap();
+
+        let mut timings = get_mock_timings();
+        timings.retain(|t| t.day == day!(2) || t.day == day!(4));
+        timings[0].part_1 = timing(5);
+        timings[0].total_nanos = 4.5e7;
+        timings.truncate(1);
+
+        // day 2 is updated, day 4 was selected but is no longer solved, day 1 is kept.
+        let run = Run {
+            timings,
+            total_millis: 45.0,
+            days: "2-4".parse().unwrap(),
+            ..Run::default()
+        };
+        let s = render(&s, run).unwrap();
+
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `40.0ms` |",
+            "",
+            "**Total: 75.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn refuses_to_merge_another_statistic() {
+        let readme = format!("{}\n{}", MARKER, MARKER);
+        let s = render(&readme, Run::default()).unwrap();
+
+        let run = Run {
+            timings: get_mock_timings()[1..2].to_vec(),
+            total_millis: 70.0,
+            statistic: Statistic::Median,
+            days: "2".parse().unwrap(),
+            ..Run::default()
+        };
+
+        assert!(matches!(render(&s, run), Err(Error::Mismatch(_))));
+    }
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("74.0ns"), Some(Duration::from_nanos(74)));
+        assert_eq!(parse_duration("3.5µs"), Some(Duration::from_nanos(3500)));
+        assert_eq!(parse_duration("2.0s"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_duration("-"), None);
+    }
 }
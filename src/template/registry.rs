@@ -0,0 +1,58 @@
+/// Registry of the solutions that are compiled into the `registry` binary, which lets `all` run
+/// every day in-process. `all` builds it on demand and runs each day's binary if that fails.
+/// The `solution!` macro defines a [`Solution`] for each day, and `build.rs` collects them from `src/bin`.
+use std::io;
+
+use crate::template::input::InputSource;
+use crate::template::report::PartReport;
+use crate::template::runner::RunOptions;
+use crate::{Day, Year};
+
+/// Entry point of a day's solution.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    /// [`None`] for solutions of the year of the flat layout, see [`crate::template::paths`].
+    pub year: Option<Year>,
+    pub day: Day,
+    /// Runs both parts against `input`, printing a report for each.
+    pub run: fn(&'static str, RunOptions) -> Vec<PartReport>,
+}
+
+#[must_use]
+pub fn find(registry: &[Solution], year: Option<Year>, day: Day) -> Option<&Solution> {
+    registry.iter().find(|s| s.year == year && s.day == day)
+}
+
+/// Runs a solution against its puzzle input.
+pub fn run(solution: &Solution, options: RunOptions) -> io::Result<Vec<PartReport>> {
+    let input = InputSource::Inputs.read(solution.year, solution.day)?;
+    // the input is leaked for the same reason as in `solution!`, parts may outlive this call.
+    let input: &'static str = Box::leak(input.into_boxed_str());
+    Ok((solution.run)(input, options))
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{find, Solution};
+    use crate::{day, year};
+
+    #[test]
+    fn finds_solution_for_day() {
+        let registry = [
+            Solution {
+                year: None,
+                day: day!(1),
+                run: |_, _| vec![],
+            },
+            Solution {
+                year: Some(year!(2022)),
+                day: day!(8),
+                run: |_, _| vec![],
+            },
+        ];
+        let year = Some(year!(2022));
+        assert_eq!(find(&registry, year, day!(8)).map(|s| s.day), Some(day!(8)));
+        assert!(find(&registry, None, day!(8)).is_none());
+        assert!(find(&registry, year, day!(1)).is_none());
+    }
+}
@@ -0,0 +1,586 @@
+/// Machine-readable records describing the outcome of a solution part.
+/// The runner emits one record per part when invoked with `--json`, and the `all` command consumes them.
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::template::alloc::AllocStats;
+use crate::template::cpu::{Clock, CpuTime};
+use crate::template::stats::{Statistic, Summary};
+use crate::Day;
+
+/// Starts every record in the output of a solution binary, so that records can not be confused
+/// with lines that the solution prints itself. The first character is the ASCII record separator.
+/// Records only pass between the commands and the solution binaries, `solve --json` prints them
+/// as plain JSON.
+pub const RECORD_PREFIX: &str = "\x1eAOC_RECORD ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// Solved, but the answer does not match the stored answer.
+    Wrong,
+    Unsolved,
+    TimedOut,
+    Panicked,
+}
+
+impl Status {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Wrong => "wrong",
+            Status::Unsolved => "unsolved",
+            Status::TimedOut => "timeout",
+            Status::Panicked => "panicked",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Status {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "wrong" => Ok(Status::Wrong),
+            "unsolved" => Ok(Status::Unsolved),
+            "timeout" => Ok(Status::TimedOut),
+            "panicked" => Ok(Status::Panicked),
+            _ => Err(Error::Field("status")),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Json(serde_json::Error),
+    Field(&'static str),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Json(e) => write!(f, "{e}"),
+            Error::Field(name) => write!(f, "missing or invalid field `{name}`"),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
+
+/// The result of running one part of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The stored correct answer, if one is known.
+    pub expected: Option<String>,
+    /// The wall time of a single run, or the selected statistic if the part was benchmarked.
+    pub duration: Duration,
+    pub samples: u128,
+    pub statistic: Statistic,
+    pub stats: Option<Summary>,
+    /// Time spent in named spans, in order of first use.
+    pub spans: Vec<(String, Duration)>,
+    /// Heap allocations of a single run, if allocation accounting is enabled.
+    pub allocations: Option<AllocStats>,
+    /// CPU time of a single run, averaged over all samples if the part was benchmarked.
+    pub cpu: Option<CpuTime>,
+    /// The clock that represents this part, see [`PartReport::reported_duration`].
+    pub clock: Clock,
+    pub status: Status,
+    /// Describes why the part did not finish, for [`Status::TimedOut`] and [`Status::Panicked`].
+    pub error: Option<String>,
+}
+
+impl PartReport {
+    #[must_use]
+    pub fn new(
+        day: Day,
+        part: u8,
+        answer: Option<String>,
+        duration: Duration,
+        samples: u128,
+    ) -> Self {
+        let status = if answer.is_some() {
+            Status::Solved
+        } else {
+            Status::Unsolved
+        };
+
+        Self {
+            day,
+            part,
+            answer,
+            expected: None,
+            duration,
+            samples,
+            statistic: Statistic::default(),
+            stats: None,
+            spans: vec![],
+            allocations: None,
+            cpu: None,
+            clock: Clock::default(),
+            status,
+            error: None,
+        }
+    }
+
+    /// Attaches the stored answer and marks the report as [`Status::Wrong`] if the answer differs.
+    #[must_use]
+    pub fn with_expected(mut self, expected: Option<String>) -> Self {
+        if self.status == Status::Solved && expected.is_some() && self.answer != expected {
+            self.status = Status::Wrong;
+        }
+        self.expected = expected;
+        self
+    }
+
+    /// Whether the answer was checked against a stored answer and matched.
+    #[must_use]
+    pub fn is_verified(&self) -> bool {
+        self.status == Status::Solved && self.expected.is_some()
+    }
+
+    /// Creates the report for a part that did not finish.
+    #[must_use]
+    pub fn failed(day: Day, part: u8, status: Status, error: String) -> Self {
+        Self {
+            status,
+            error: Some(error),
+            ..Self::new(day, part, None, Duration::ZERO, 0)
+        }
+    }
+
+    /// Attaches the summary of a benchmark and the statistic that `duration` was taken from.
+    #[must_use]
+    pub fn with_stats(mut self, statistic: Statistic, stats: Summary) -> Self {
+        self.statistic = statistic;
+        self.stats = Some(stats);
+        self
+    }
+
+    #[must_use]
+    pub fn with_spans(mut self, spans: Vec<(String, Duration)>) -> Self {
+        self.spans = spans;
+        self
+    }
+
+    #[must_use]
+    pub fn with_allocations(mut self, allocations: Option<AllocStats>) -> Self {
+        self.allocations = allocations;
+        self
+    }
+
+    #[must_use]
+    pub fn with_cpu(mut self, clock: Clock, cpu: Option<CpuTime>) -> Self {
+        self.clock = clock;
+        self.cpu = cpu;
+        self
+    }
+
+    /// The duration measured by the selected clock. Falls back to wall time if no CPU time is available.
+    #[must_use]
+    pub fn reported_duration(&self) -> Duration {
+        match (self.clock, &self.cpu) {
+            (Clock::Cpu, Some(cpu)) => cpu.total(),
+            _ => self.duration,
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let spans: Vec<Value> = self
+            .spans
+            .iter()
+            .map(|(name, duration)| json!({"name": name, "nanos": duration.as_nanos()}))
+            .collect();
+
+        json!({
+            "day": self.day.into_inner(),
+            "part": self.part,
+            "answer": self.answer,
+            "expected": self.expected,
+            "duration_nanos": self.duration.as_nanos(),
+            "samples": self.samples,
+            "statistic": self.statistic.as_str(),
+            "stats": self.stats.as_ref().map(Summary::to_json),
+            "spans": spans,
+            "allocations": self.allocations.as_ref().map(AllocStats::to_json),
+            "cpu": self.cpu.as_ref().map(CpuTime::to_json),
+            "clock": self.clock.as_str(),
+            "status": self.status.as_str(),
+            "error": self.error,
+        })
+        .to_string()
+    }
+
+    /// Formats the report as a line of the output of a solution binary, see [`RECORD_PREFIX`].
+    #[must_use]
+    pub fn to_record(&self) -> String {
+        format!("{RECORD_PREFIX}{}", self.to_json())
+    }
+
+    /// Parses a line of the output of a solution binary, [`None`] if it is not a record.
+    #[must_use]
+    pub fn from_record(line: &str) -> Option<Result<Self, Error>> {
+        line.strip_prefix(RECORD_PREFIX).map(Self::from_json)
+    }
+
+    /// Parses a record previously created with [`PartReport::to_json`].
+    pub fn from_json(s: &str) -> Result<Self, Error> {
+        let value = serde_json::from_str::<Value>(s)?;
+
+        let day = value
+            .get("day")
+            .and_then(Value::as_u64)
+            .and_then(|d| u8::try_from(d).ok())
+            .and_then(Day::new)
+            .ok_or(Error::Field("day"))?;
+
+        let part = value
+            .get("part")
+            .and_then(Value::as_u64)
+            .and_then(|p| u8::try_from(p).ok())
+            .ok_or(Error::Field("part"))?;
+
+        let answer = match value.get("answer") {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Null) => None,
+            _ => return Err(Error::Field("answer")),
+        };
+
+        let expected = match value.get("expected") {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Null) => None,
+            _ => return Err(Error::Field("expected")),
+        };
+
+        let duration = value
+            .get("duration_nanos")
+            .and_then(Value::as_u64)
+            .map(Duration::from_nanos)
+            .ok_or(Error::Field("duration_nanos"))?;
+
+        let samples = value
+            .get("samples")
+            .and_then(Value::as_u64)
+            .map(u128::from)
+            .ok_or(Error::Field("samples"))?;
+
+        let statistic = value
+            .get("statistic")
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse().ok())
+            .ok_or(Error::Field("statistic"))?;
+
+        let stats = match value.get("stats") {
+            Some(Value::Null) => None,
+            Some(v) => Some(Summary::from_json(v).ok_or(Error::Field("stats"))?),
+            None => return Err(Error::Field("stats")),
+        };
+
+        let spans = value
+            .get("spans")
+            .and_then(Value::as_array)
+            .ok_or(Error::Field("spans"))?
+            .iter()
+            .map(|span| {
+                let name = span.get("name")?.as_str()?.to_string();
+                let nanos = span.get("nanos")?.as_u64()?;
+                Some((name, Duration::from_nanos(nanos)))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or(Error::Field("spans"))?;
+
+        let allocations = match value.get("allocations") {
+            Some(Value::Null) => None,
+            Some(v) => Some(AllocStats::from_json(v).ok_or(Error::Field("allocations"))?),
+            None => return Err(Error::Field("allocations")),
+        };
+
+        let cpu = match value.get("cpu") {
+            Some(Value::Null) => None,
+            Some(v) => Some(CpuTime::from_json(v).ok_or(Error::Field("cpu"))?),
+            None => return Err(Error::Field("cpu")),
+        };
+
+        let clock = value
+            .get("clock")
+            .and_then(Value::as_str)
+            .and_then(|s| s.parse().ok())
+            .ok_or(Error::Field("clock"))?;
+
+        let status = value
+            .get("status")
+            .and_then(Value::as_str)
+            .ok_or(Error::Field("status"))?
+            .parse()?;
+
+        let error = match value.get("error") {
+            Some(Value::String(s)) => Some(s.clone()),
+            Some(Value::Null) => None,
+            _ => return Err(Error::Field("error")),
+        };
+
+        Ok(Self {
+            day,
+            part,
+            answer,
+            expected,
+            duration,
+            samples,
+            statistic,
+            stats,
+            spans,
+            allocations,
+            cpu,
+            clock,
+            status,
+            error,
+        })
+    }
+}
+
+/// Counts the outcomes of parts for the summary at the end of a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Tally {
+    /// Answers that match their stored answer.
+    pub correct: usize,
+    pub wrong: usize,
+    /// Answers without a stored answer to compare against.
+    pub unverified: usize,
+    pub unsolved: usize,
+    pub panicked: usize,
+    pub timed_out: usize,
+    /// Days of which the solution did not compile.
+    pub not_compiled: usize,
+    /// Days of which the solution failed outside of its parts, e.g. while reading the input.
+    pub failed: usize,
+}
+
+impl Tally {
+    pub fn add(&mut self, report: &PartReport) {
+        match report.status {
+            Status::Solved if report.is_verified() => self.correct += 1,
+            Status::Solved => self.unverified += 1,
+            Status::Wrong => self.wrong += 1,
+            Status::Unsolved => self.unsolved += 1,
+            Status::Panicked => self.panicked += 1,
+            Status::TimedOut => self.timed_out += 1,
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a PartReport> for Tally {
+    fn from_iter<T: IntoIterator<Item = &'a PartReport>>(iter: T) -> Self {
+        let mut tally = Self::default();
+        iter.into_iter().for_each(|report| tally.add(report));
+        tally
+    }
+}
+
+impl Display for Tally {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} correct, {} wrong, {} without stored answer, {} unsolved, {} panicked, {} timed out",
+            self.correct, self.wrong, self.unverified, self.unsolved, self.panicked, self.timed_out
+        )?;
+
+        // failures of whole days are rare, so they are only mentioned when they happened.
+        for (count, what) in [
+            (self.not_compiled, "did not compile"),
+            (self.failed, "failed"),
+        ] {
+            match count {
+                0 => {}
+                1 => write!(f, ", 1 day {what}")?,
+                _ => write!(f, ", {count} days {what}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{PartReport, Status, Tally};
+    use crate::day;
+    use crate::template::alloc::AllocStats;
+    use crate::template::cpu::{Clock, CpuTime};
+    use crate::template::stats::{Statistic, Summary};
+
+    #[test]
+    fn roundtrip() {
+        let report = PartReport::new(
+            day!(8),
+            1,
+            Some("42 (@ 3 samples)".into()),
+            Duration::from_nanos(74),
+            100_000,
+        );
+        assert_eq!(PartReport::from_json(&report.to_json()).unwrap(), report);
+    }
+
+    #[test]
+    fn roundtrip_with_stats() {
+        let samples = [Duration::from_nanos(70), Duration::from_nanos(90)];
+        let stats = Summary::from_samples(&samples, false);
+        let report = PartReport::new(day!(5), 2, Some("46".into()), stats.median, 2)
+            .with_stats(Statistic::Median, stats);
+        let parsed = PartReport::from_json(&report.to_json()).unwrap();
+        assert_eq!(parsed.statistic, Statistic::Median);
+        assert_eq!(parsed, report);
+    }
+
+    #[test]
+    fn roundtrip_with_spans() {
+        let report = PartReport::new(day!(8), 2, Some("6".into()), Duration::from_nanos(90), 1)
+            .with_spans(vec![
+                ("parse".into(), Duration::from_nanos(30)),
+                ("solve".into(), Duration::from_nanos(60)),
+            ]);
+        assert_eq!(PartReport::from_json(&report.to_json()).unwrap(), report);
+    }
+
+    #[test]
+    fn roundtrip_with_allocations() {
+        let report = PartReport::new(day!(5), 2, Some("46".into()), Duration::from_nanos(9), 1)
+            .with_allocations(Some(AllocStats {
+                allocations: 12,
+                bytes: 4096,
+                peak_bytes: 2048,
+            }));
+        assert_eq!(PartReport::from_json(&report.to_json()).unwrap(), report);
+    }
+
+    #[test]
+    fn roundtrip_with_cpu() {
+        let cpu = CpuTime {
+            user: Duration::from_nanos(60),
+            system: Duration::from_nanos(20),
+        };
+        let report = PartReport::new(day!(5), 1, Some("35".into()), Duration::from_nanos(90), 1)
+            .with_cpu(Clock::Cpu, Some(cpu));
+        assert_eq!(report.reported_duration(), Duration::from_nanos(80));
+        assert_eq!(PartReport::from_json(&report.to_json()).unwrap(), report);
+
+        let wall = report.clone().with_cpu(Clock::Wall, Some(cpu));
+        assert_eq!(wall.reported_duration(), Duration::from_nanos(90));
+    }
+
+    #[test]
+    fn failed_part() {
+        let report = PartReport::failed(
+            day!(8),
+            1,
+            Status::Panicked,
+            "panicked: called `Option::unwrap()` on a `None` value".into(),
+        );
+        let json = report.to_json();
+        assert!(json.contains("\"status\":\"panicked\""));
+        assert_eq!(PartReport::from_json(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn verifies_expected_answer() {
+        let report = |answer: &str| {
+            PartReport::new(day!(7), 1, Some(answer.into()), Duration::from_nanos(1), 1)
+        };
+
+        let correct = report("6440").with_expected(Some("6440".into()));
+        assert_eq!(correct.status, Status::Solved);
+        assert!(correct.is_verified());
+
+        let wrong = report("6441").with_expected(Some("6440".into()));
+        assert_eq!(wrong.status, Status::Wrong);
+        assert!(!wrong.is_verified());
+        assert_eq!(PartReport::from_json(&wrong.to_json()).unwrap(), wrong);
+
+        let unknown = report("6441").with_expected(None);
+        assert_eq!(unknown.status, Status::Solved);
+        assert!(!unknown.is_verified());
+    }
+
+    #[test]
+    fn unsolved_part() {
+        let report = PartReport::new(day!(1), 2, None, Duration::from_nanos(10), 1);
+        assert_eq!(report.status, Status::Unsolved);
+        let json = report.to_json();
+        assert!(json.contains("\"answer\":null"));
+        assert_eq!(PartReport::from_json(&json).unwrap(), report);
+    }
+
+    #[test]
+    fn rejects_human_output() {
+        assert!(PartReport::from_json("Part 1: 42 (74.13ns @ 100000 samples)").is_err());
+        assert!(PartReport::from_json("{\"day\":1}").is_err());
+    }
+
+    #[test]
+    fn parses_records() {
+        let report = PartReport::new(day!(8), 1, Some("6".into()), Duration::from_nanos(9), 1);
+        let record = report.to_record();
+        assert_eq!(PartReport::from_record(&record).unwrap().unwrap(), report);
+
+        // solutions may print anything, including lines that look like json.
+        assert!(PartReport::from_record("{1, 2}").is_none());
+        assert!(PartReport::from_record(&report.to_json()).is_none());
+        assert!(PartReport::from_record("\x1eAOC_RECORD {1, 2}")
+            .unwrap()
+            .is_err());
+    }
+
+    #[test]
+    fn tallies_outcomes() {
+        let solved = |answer: &str, expected: Option<&str>| {
+            PartReport::new(day!(1), 1, Some(answer.into()), Duration::ZERO, 1)
+                .with_expected(expected.map(Into::into))
+        };
+
+        let reports = [
+            solved("1", Some("1")),
+            solved("2", Some("3")),
+            solved("4", None),
+            PartReport::new(day!(1), 2, None, Duration::ZERO, 1),
+            PartReport::failed(day!(2), 1, Status::Panicked, "panicked".into()),
+            PartReport::failed(day!(2), 2, Status::TimedOut, "timed out".into()),
+        ];
+
+        let tally: Tally = reports.iter().collect();
+        assert_eq!(
+            tally.to_string(),
+            "1 correct, 1 wrong, 1 without stored answer, 1 unsolved, 1 panicked, 1 timed out"
+        );
+
+        let tally = Tally {
+            not_compiled: 1,
+            failed: 2,
+            ..tally
+        };
+        assert_eq!(
+            tally.to_string(),
+            "1 correct, 1 wrong, 1 without stored answer, 1 unsolved, 1 panicked, 1 timed out, 1 day did not compile, 2 days failed"
+        );
+    }
+}
@@ -1,24 +1,598 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::alloc::{self, format_alloc_stats, AllocStats};
+use crate::template::commands::Error;
+use crate::template::cpu::{self, Clock, CpuTime};
+use crate::template::input::InputSource;
+use crate::template::report::{PartReport, Status, Tally};
+use crate::template::stats::{Statistic, Summary};
+use crate::template::submission::{self, Submission, Verdict};
+use crate::template::{answers, client, spans, ANSI_ITALIC, ANSI_RESET};
+use crate::{Day, Year};
+use std::any::Any;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
-    let part_str = format!("Part {part}");
+/// Set once a part timed out. Its thread keeps running, and both `getrusage` and the counting
+/// allocator attribute its work to the whole process, so CPU time and allocations are no longer
+/// reported for later parts.
+static ABANDONED_PART: AtomicBool = AtomicBool::new(false);
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Options that control how the runner executes solution parts.
+/// Commands pass them on to solution binaries as command-line arguments.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RunOptions {
+    /// Benchmark each part after the first run.
+    pub time: bool,
+    /// Emit records instead of human-readable output, see [`RECORD_PREFIX`].
+    /// `solve --json` turns them into plain JSON lines.
+    ///
+    /// [`RECORD_PREFIX`]: crate::template::report::RECORD_PREFIX
+    pub json: bool,
+    /// Give up on a part if its first run takes longer than this.
+    pub timeout: Option<Duration>,
+    /// The clock that represents a part in the output.
+    pub clock: Clock,
+    pub bench: BenchOptions,
+    /// Only run this part, both parts run if [`None`].
+    pub part: Option<u8>,
+}
+
+impl RunOptions {
+    /// Reads the options from the arguments passed to a solution binary.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let timeout = match get_arg_value("--timeout").map(|s| parse_seconds(&s)) {
+            None => None,
+            Some(Ok(timeout)) => Some(timeout),
+            Some(Err(e)) => {
+                eprintln!("Unexpected value for --timeout: {e}");
+                process::exit(1);
+            }
+        };
+
+        let part = match get_arg_value("--part").map(|s| parse_part(&s)) {
+            None => None,
+            Some(Ok(part)) => Some(part),
+            Some(Err(e)) => {
+                eprintln!("Unexpected value for --part: {e}");
+                process::exit(1);
+            }
+        };
+
+        let clock = match get_arg_value("--clock").map(|s| s.parse()) {
+            None => Clock::default(),
+            Some(Ok(clock)) => clock,
+            Some(Err(e)) => {
+                eprintln!("Unexpected value for --clock: {e}");
+                process::exit(1);
+            }
+        };
+
+        Self {
+            time: env::args().any(|x| x == "--time"),
+            json: env::args().any(|x| x == "--json"),
+            timeout,
+            clock,
+            bench: BenchOptions::from_env(),
+            part,
+        }
+    }
+
+    /// Whether `part` is selected to run.
+    #[must_use]
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|selected| selected == part)
+    }
+
+    /// Converts the options into arguments for a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.time {
+            args.push("--time".into());
+            args.extend(self.bench.to_args());
+        }
+
+        if self.json {
+            args.push("--json".into());
+        }
+
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".into());
+            args.push(timeout.as_secs_f64().to_string());
+        }
+
+        if self.clock != Clock::default() {
+            args.push("--clock".into());
+            args.push(self.clock.to_string());
+        }
+
+        if let Some(part) = self.part {
+            args.push("--part".into());
+            args.push(part.to_string());
+        }
+
+        args
+    }
+}
+
+/// Options that control how solution parts are benchmarked with `--time`.
+/// Defaults can be configured with the `AOC_BENCH_*` variables in `.cargo/config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// The statistic that represents a benchmark in the output.
+    pub statistic: Statistic,
+    /// Discard samples outside of 1.5 times the interquartile range.
+    pub reject_outliers: bool,
+    /// Approximate time to spend on sampling a part.
+    pub budget: Duration,
+    pub min_samples: u128,
+    pub max_samples: u128,
+    /// Number of untimed runs before sampling starts.
+    pub warmup: u128,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            statistic: Statistic::default(),
+            reject_outliers: false,
+            budget: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10000,
+            warmup: 0,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Reads the configured defaults from the `AOC_BENCH_TIME`, `AOC_BENCH_MIN_SAMPLES`,
+    /// `AOC_BENCH_MAX_SAMPLES` and `AOC_BENCH_WARMUP` environment variables.
+    pub fn from_config() -> Result<Self, String> {
+        let defaults = Self::default();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+        let options = Self {
+            budget: read_config("AOC_BENCH_TIME", parse_seconds)?.unwrap_or(defaults.budget),
+            min_samples: read_config("AOC_BENCH_MIN_SAMPLES", parse_count)?
+                .unwrap_or(defaults.min_samples),
+            max_samples: read_config("AOC_BENCH_MAX_SAMPLES", parse_count)?
+                .unwrap_or(defaults.max_samples),
+            warmup: read_config("AOC_BENCH_WARMUP", parse_count)?.unwrap_or(defaults.warmup),
+            ..defaults
+        };
+
+        options.validate()
+    }
+
+    /// Checks that the sample bounds are consistent.
+    pub fn validate(self) -> Result<Self, String> {
+        if self.min_samples == 0 {
+            return Err("the minimum sample count must be at least 1".into());
+        }
+
+        if self.min_samples > self.max_samples {
+            return Err(format!(
+                "the minimum sample count ({}) exceeds the maximum ({})",
+                self.min_samples, self.max_samples
+            ));
+        }
+
+        Ok(self)
+    }
+
+    /// Reads the options from the arguments passed to a solution binary.
+    #[must_use]
+    pub fn from_env() -> Self {
+        fn arg_or_exit<T>(flag: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+            match get_arg_value(flag).map(|s| parse(&s)) {
+                None => None,
+                Some(Ok(value)) => Some(value),
+                Some(Err(e)) => {
+                    eprintln!("Unexpected value for {flag}: {e}");
+                    process::exit(1);
+                }
+            }
+        }
+
+        let config = Self::from_config().unwrap_or_else(|e| {
+            eprintln!("Invalid benchmark configuration: {e}");
+            process::exit(1);
+        });
+
+        let options = Self {
+            statistic: arg_or_exit("--stat", |s| s.parse().map_err(|e| format!("{e}")))
+                .unwrap_or_default(),
+            reject_outliers: env::args().any(|x| x == "--reject-outliers"),
+            budget: arg_or_exit("--bench-time", parse_seconds).unwrap_or(config.budget),
+            min_samples: arg_or_exit("--min-samples", parse_count).unwrap_or(config.min_samples),
+            max_samples: arg_or_exit("--max-samples", parse_count).unwrap_or(config.max_samples),
+            warmup: arg_or_exit("--warmup", parse_count).unwrap_or(config.warmup),
+        };
+
+        options.validate().unwrap_or_else(|e| {
+            eprintln!("Invalid benchmark options: {e}");
+            process::exit(1);
+        })
+    }
+
+    /// Converts the options into arguments for a solution binary.
+    /// Budget, sample bounds and warmup are always passed, so that the binary does not fall back
+    /// to a configured default that the command line overrode.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.statistic != Statistic::default() {
+            args.push("--stat".into());
+            args.push(self.statistic.to_string());
+        }
+
+        if self.reject_outliers {
+            args.push("--reject-outliers".into());
+        }
+
+        args.extend([
+            "--bench-time".into(),
+            self.budget.as_secs_f64().to_string(),
+            "--min-samples".into(),
+            self.min_samples.to_string(),
+            "--max-samples".into(),
+            self.max_samples.to_string(),
+            "--warmup".into(),
+            self.warmup.to_string(),
+        ]);
+
+        args
+    }
+
+    /// Number of samples to take for a part whose first run took `base_time`.
+    #[must_use]
+    pub fn sample_count(&self, base_time: &Duration) -> u128 {
+        (self.budget.as_nanos() / cmp::max(base_time.as_nanos(), 10))
+            .clamp(self.min_samples, self.max_samples)
+    }
+}
+
+impl Display for BenchOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} budget · {}-{} samples · {} warmup runs · {}",
+            self.budget, self.min_samples, self.max_samples, self.warmup, self.statistic
+        )?;
+        if self.reject_outliers {
+            write!(f, " · outliers rejected")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a duration given in (fractional) seconds.
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    let secs: f64 = s.parse().map_err(|_| format!("`{s}` is not a number"))?;
+    Duration::try_from_secs_f64(secs).map_err(|_| format!("`{s}` is not a valid duration"))
+}
+
+/// Parses the number of a puzzle part, i.e. 1 or 2.
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("`{s}` is not a part, expecting 1 or 2")),
+    }
+}
+
+/// Parses a non-negative count, e.g. a number of samples.
+pub fn parse_count(s: &str) -> Result<u128, String> {
+    s.parse()
+        .map_err(|_| format!("`{s}` is not a non-negative integer"))
+}
+
+fn read_config<T>(
+    name: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<Option<T>, String> {
+    match env::var(name) {
+        Ok(value) if !value.trim().is_empty() => parse(value.trim())
+            .map(Some)
+            .map_err(|e| format!("{name}: {e}")),
+        _ => Ok(None),
+    }
+}
+
+/// Runs a part from the `main` of a solution binary, [`None`] if another part was selected with `--part`.
+pub fn run_part<I, T, F>(
+    func: F,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+) -> Option<PartReport>
+where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+    F: Fn(I) -> Option<T> + Send + 'static,
+{
+    let options = RunOptions::from_env();
+    if !options.runs_part(part) {
+        return None;
+    }
+
+    let source = InputSource::from_env();
+    let (result, report) = execute_part(func, input, year, day, part, options, &source);
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        match submit_result(result, year, day, part) {
+            Some(Ok(verdict)) => {
+                print_verdict(part, &verdict);
+                match verdict {
+                    Verdict::Correct => record_correct_answer(year, &report),
+                    Verdict::WaitFor(_) => println!(
+                        "{ANSI_ITALIC}Pass `--wait` to resubmit once the cooldown expires.{ANSI_RESET}"
+                    ),
+                    _ => {}
+                }
+            }
+            Some(Err(e)) => eprintln!("Failed to submit: {e}"),
+            None => {}
+        }
+    }
+
+    Some(report)
+}
+
+/// Exits with the code of the most severe failure among the parts that ran, see [`Error::exit_code`].
+pub fn exit_with_status(reports: &[Option<PartReport>]) {
+    let tally: Tally = reports.iter().flatten().collect();
+    if let Some(e) = Error::from_tally(&tally) {
+        process::exit(e.exit_code());
+    }
+}
+
+/// Runs a part in isolation, verifies its answer if `source` is the puzzle input and prints the report.
+pub fn execute_part<I, T, F>(
+    func: F,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    options: RunOptions,
+    source: &InputSource,
+) -> (Option<T>, PartReport)
+where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+    F: Fn(I) -> Option<T> + Send + 'static,
+{
+    let (result, report) = match run_isolated(func, input, part, options) {
+        Outcome::Finished(result, measurement) => {
+            let answer = result.as_ref().map(ToString::to_string);
+            let statistic = options.bench.statistic;
+            let report = match measurement.summary {
+                Some((summary, samples)) => {
+                    PartReport::new(day, part, answer, summary.get(statistic), samples)
+                        .with_stats(statistic, summary)
+                }
+                None => PartReport::new(day, part, answer, measurement.duration, 1),
+            }
+            .with_spans(measurement.spans)
+            .with_allocations(measurement.allocations)
+            .with_cpu(options.clock, measurement.cpu)
+            .with_expected(get_expected_answer(year, day, part, source));
+            (result, report)
+        }
+        Outcome::TimedOut(timeout) => (
+            None,
+            PartReport::failed(
+                day,
+                part,
+                Status::TimedOut,
+                format!("timed out after {timeout:?}"),
+            ),
+        ),
+        Outcome::Panicked(message) => (
+            None,
+            PartReport::failed(day, part, Status::Panicked, format!("panicked: {message}")),
+        ),
+    };
+
+    if options.json {
+        println!("{}", report.to_record());
+    } else {
+        print_report(&report);
+    }
+
+    (result, report)
+}
+
+/// Looks up the stored answer for a part. Answers only apply to the puzzle input, not to examples or other files.
+fn get_expected_answer(
+    year: Option<Year>,
+    day: Day,
+    part: u8,
+    source: &InputSource,
+) -> Option<String> {
+    if *source != InputSource::Inputs {
+        return None;
+    }
+
+    match answers::read(year, day) {
+        Ok(answers) => answers.get(part).map(ToString::to_string),
+        Err(e) => {
+            eprintln!("{e}");
+            None
+        }
+    }
+}
+
+/// Stores the answer of a part whose submission was accepted.
+fn record_correct_answer(year: Option<Year>, report: &PartReport) {
+    let Some(answer) = &report.answer else {
+        return;
+    };
+
+    if report.expected.as_ref() == Some(answer) {
+        return;
+    }
+
+    match answers::record(year, report.day, report.part, answer) {
+        Ok(()) => println!(
+            "Recorded answer in \"{}\".",
+            answers::get_path(year, report.day).display()
+        ),
+        Err(e) => eprintln!("{e}"),
+    }
+}
+
+/// Prints the verdict on a submitted answer, highlighted so that it stands out from the reports.
+pub fn print_verdict(part: u8, verdict: &Verdict) {
+    let symbol = match verdict {
+        Verdict::Correct => "✔ ",
+        Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong => "✘ ",
+        Verdict::WaitFor(_) | Verdict::AlreadyCompleted => "",
+    };
+    println!("{ANSI_BOLD}{symbol}Part {part} submission: {verdict}{ANSI_RESET}");
+}
+
+/// Prints the final output for a part, followed by the benchmark summary if there is one.
+pub fn print_report(report: &PartReport) {
+    if let Some(error) = &report.error {
+        print!("\r");
+        println!("Part {}: {error}", report.part);
+        return;
+    }
+
+    // cpu time is averaged over all samples, so the selected statistic does not apply to it.
+    let statistic = match report.clock {
+        Clock::Wall => report.statistic,
+        Clock::Cpu => Statistic::Mean,
+    };
+    let mut duration_str = format_duration(&report.reported_duration(), report.samples, statistic);
+    duration_str.push_str(&format_verification(report));
+
+    print_result(
+        &report.answer,
+        &format!("Part {}", report.part),
+        &duration_str,
+    );
+
+    if let Some(cpu) = &report.cpu {
+        let statistic = report.stats.map(|_| report.statistic);
+        println!("{}", format_cpu(&report.duration, cpu, statistic));
+    }
+
+    if !report.spans.is_empty() {
+        println!("{}", format_spans(&report.spans));
+    }
+
+    if let Some(allocations) = &report.allocations {
+        println!(
+            "  {ANSI_ITALIC}{}{ANSI_RESET}",
+            format_alloc_stats(allocations)
+        );
+    }
+
+    if let Some(stats) = &report.stats {
+        println!("{}", format_summary(stats));
+    }
+}
+
+/// Timings collected while running a part.
+struct Measurement {
+    /// Duration of the first run.
+    duration: Duration,
+    /// Time spent in named spans, averaged over all runs.
+    spans: Vec<(String, Duration)>,
+    /// Benchmark summary and sample count, if the part was benched.
+    summary: Option<(Summary, u128)>,
+    /// Heap allocations of the first run, if the `alloc_stats` feature is enabled.
+    allocations: Option<AllocStats>,
+    /// CPU time of the first run, or averaged over all samples if the part was benched.
+    cpu: Option<CpuTime>,
+}
+
+/// How running a part on its own thread ended.
+enum Outcome<T> {
+    Finished(Option<T>, Box<Measurement>),
+    TimedOut(Duration),
+    Panicked(String),
+}
+
+enum Message<T> {
+    FirstRun,
+    Done(Option<T>, Box<Measurement>),
+}
+
+/// Runs a part on a separate thread so that panics and endless loops do not take down the binary.
+/// The timeout only applies to the first run; once a part has returned, benchmarking is allowed to finish.
+fn run_isolated<I, T, F>(func: F, input: I, part: u8, options: RunOptions) -> Outcome<T>
+where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+    F: Fn(I) -> Option<T> + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+
+    let worker = thread::Builder::new()
+        .name(format!("part {part}"))
+        .spawn(move || {
+            let part_str = format!("Part {part}");
+            let hook_tx = tx.clone();
+
+            let (result, measurement) = run_timed(func, input, &options, |result| {
+                let _ = hook_tx.send(Message::FirstRun);
+                if !options.json {
+                    print_result(result, &part_str, "");
+                }
+            });
+
+            let _ = tx.send(Message::Done(result, Box::new(measurement)));
+        })
+        .expect("failed to spawn thread for solution part");
+
+    let first = match options.timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+
+    match first {
+        Err(RecvTimeoutError::Timeout) => {
+            // the thread cannot be stopped, it is left running until the binary exits.
+            ABANDONED_PART.store(true, Ordering::Relaxed);
+            return Outcome::TimedOut(options.timeout.unwrap_or_default());
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            return Outcome::Panicked(panic_message(worker.join()));
+        }
+        Ok(_) => {}
+    }
+
+    match rx.recv() {
+        Ok(Message::Done(result, measurement)) => Outcome::Finished(result, measurement),
+        _ => Outcome::Panicked(panic_message(worker.join())),
+    }
+}
+
+fn panic_message(result: thread::Result<()>) -> String {
+    let Err(payload) = result else {
+        return "thread exited without a result".into();
+    };
+
+    let payload: &(dyn Any + Send) = &*payload;
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        (*s).to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic payload".into()
     }
 }
 
@@ -28,38 +602,63 @@ pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, d
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Measurement) {
+    spans::reset();
+    alloc::reset();
+    let cpu_start = cpu_now();
     let timer = Instant::now();
     let result = func(input.clone());
     let base_time = timer.elapsed();
+    let base_cpu = cpu_now().zip(cpu_start).map(|(end, start)| end - start);
+    let allocations = alloc_snapshot();
+    let base_spans = spans::take();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let measurement = if options.time {
+        let (summary, samples, spans, cpu) = bench(func, input, &base_time, options);
+        Measurement {
+            duration: base_time,
+            spans,
+            summary: Some((summary, samples)),
+            allocations,
+            cpu,
+        }
     } else {
-        (base_time, 1)
+        Measurement {
+            duration: base_time,
+            spans: base_spans,
+            summary: None,
+            allocations,
+            cpu: base_cpu,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, measurement)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &RunOptions,
+) -> (Summary, u128, Vec<(String, Duration)>, Option<CpuTime>) {
+    if !options.json {
+        let mut stdout = stdout();
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
+    let bench_iterations = options.bench.sample_count(base_time);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..options.bench.warmup {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    spans::reset();
+    let cpu_start = cpu_now();
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -69,30 +668,115 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
+    let cpu = cpu_now()
+        .zip(cpu_start)
+        .map(|(end, start)| (end - start).per_run(bench_iterations));
+
+    // same as `CpuTime::per_run`, the sample count is at least 1 but may exceed `u32`.
+    let runs = u32::try_from(bench_iterations).unwrap_or(u32::MAX).max(1);
+    let spans = spans::take()
+        .into_iter()
+        .map(|(name, total)| (name, total / runs))
+        .collect();
+
     (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
+        Summary::from_samples(&timers, options.bench.reject_outliers),
         bench_iterations,
+        spans,
+        cpu,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// The CPU time of the process, [`None`] once it includes a part that was given up on.
+fn cpu_now() -> Option<CpuTime> {
+    if ABANDONED_PART.load(Ordering::Relaxed) {
+        None
+    } else {
+        cpu::now()
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+/// The allocations since the last [`alloc::reset`], [`None`] once they may include a part that was
+/// given up on.
+fn alloc_snapshot() -> Option<AllocStats> {
+    if ABANDONED_PART.load(Ordering::Relaxed) {
+        None
+    } else {
+        alloc::snapshot()
+    }
+}
+
+/// Returns the value following `flag` in the arguments passed to the binary.
+fn get_arg_value(flag: &str) -> Option<String> {
+    let mut args = env::args().skip_while(|x| x != flag);
+    args.next()?;
+    args.next()
+}
+
+#[must_use]
+pub fn format_duration(duration: &Duration, samples: u128, statistic: Statistic) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
-    } else {
+    } else if statistic == Statistic::Mean {
         format!(" ({duration:.1?} @ {samples} samples)")
+    } else {
+        format!(" ({duration:.1?} {statistic} @ {samples} samples)")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Formats whether the answer matched the stored answer, or an empty string if there is none.
+#[must_use]
+pub fn format_verification(report: &PartReport) -> String {
+    match (&report.status, &report.expected) {
+        (Status::Solved, Some(_)) => " ✔".into(),
+        (Status::Wrong, Some(expected)) => format!(" ✘ expected {ANSI_BOLD}{expected}{ANSI_RESET}"),
+        _ => String::new(),
+    }
+}
+
+/// Formats wall and CPU time side by side. For a benchmark, `statistic` is the statistic that
+/// wall time was taken from, while CPU time is always the mean over all samples.
+#[must_use]
+pub fn format_cpu(wall: &Duration, cpu: &CpuTime, statistic: Option<Statistic>) -> String {
+    let (wall_label, cpu_label) = match statistic {
+        Some(statistic) => (format!("wall {statistic}"), "cpu mean"),
+        None => ("wall".to_string(), "cpu"),
+    };
+
+    format!(
+        "  {ANSI_ITALIC}{wall_label} {wall:.1?} · {cpu_label} {:.1?} (user {:.1?} · sys {:.1?}){ANSI_RESET}",
+        cpu.total(),
+        cpu.user,
+        cpu.system
+    )
+}
+
+#[must_use]
+pub fn format_spans(spans: &[(String, Duration)]) -> String {
+    let spans: Vec<String> = spans
+        .iter()
+        .map(|(name, duration)| format!("{name} {duration:.1?}"))
+        .collect();
+
+    format!("  {ANSI_ITALIC}{}{ANSI_RESET}", spans.join(" · "))
+}
+
+#[must_use]
+pub fn format_summary(stats: &Summary) -> String {
+    let mut s = format!(
+        "  {ANSI_ITALIC}mean {:.1?} · median {:.1?} · min {:.1?} · max {:.1?} · σ {:.1?} · p95 {:.1?} · p99 {:.1?}",
+        stats.mean, stats.median, stats.min, stats.max, stats.stddev, stats.p95, stats.p99
+    );
+
+    if stats.outliers > 0 {
+        s.push_str(&format!(" ({} outliers rejected)", stats.outliers));
+    }
+
+    s.push_str(ANSI_RESET);
+    s
+}
+
+pub fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
@@ -127,14 +811,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Parse the arguments passed to `solve` and try to submit one part of the solution,
+/// if it was computed from the puzzle input and previous submissions do not rule it out.
 fn submit_result<T: Display>(
     result: T,
+    year: Option<Year>,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Verdict, client::Error>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +841,123 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if InputSource::from_env() != InputSource::Inputs {
+        eprintln!("Refusing to submit a result that was not computed from the puzzle input.");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+
+    // every wrong answer extends the lockout of the website, so known mistakes are not sent.
+    match submission::load(year, day).map(|history| history.check(part, &answer)) {
+        Ok(Ok(())) => {}
+        Ok(Err(refusal)) => {
+            println!("{ANSI_BOLD}✘ Part {part} submission: refused, {refusal}{ANSI_RESET}");
+            return None;
+        }
+        Err(e) => eprintln!("{e}"),
+    }
+
+    let submit = || {
+        let verdict = client::submit(year, day, part, &answer)?;
+        if let Err(e) = submission::record(year, day, Submission::now(part, &answer, verdict)) {
+            eprintln!("{e}");
+        }
+        Ok(verdict)
+    };
+
+    if args.contains(&"--wait".into()) {
+        Some(client::retry_after_cooldown(submit, |wait| {
+            print_verdict(part, &Verdict::WaitFor(wait));
+            countdown(wait + client::COOLDOWN_MARGIN);
+        }))
+    } else {
+        Some(submit())
+    }
+}
+
+/// Counts down the time until `wait` has passed on a single line, which is cleared afterwards.
+fn countdown(wait: Duration) {
+    let end = Instant::now() + wait;
+
+    while let Some(left) = end.checked_duration_since(Instant::now()) {
+        // round up, so that the countdown ends at 1s rather than 0s.
+        let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+        print!(
+            "\r{ANSI_ITALIC}Resubmitting in {}...{ANSI_RESET}    ",
+            submission::format_wait(Duration::from_secs(secs))
+        );
+        let _ = stdout().flush();
+        thread::sleep(left.min(Duration::from_secs(1)));
+    }
+
+    print!("\r{}\r", " ".repeat(40));
+    let _ = stdout().flush();
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_cpu, parse_part, BenchOptions, RunOptions};
+    use crate::template::cpu::CpuTime;
+    use crate::template::stats::Statistic;
+    use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn sample_count_respects_budget_and_bounds() {
+        let options = BenchOptions {
+            budget: Duration::from_millis(100),
+            min_samples: 5,
+            max_samples: 50,
+            ..BenchOptions::default()
+        };
+        assert_eq!(options.sample_count(&Duration::from_millis(4)), 25);
+        assert_eq!(options.sample_count(&Duration::from_secs(1)), 5);
+        assert_eq!(options.sample_count(&Duration::from_nanos(1)), 50);
+    }
+
+    #[test]
+    fn validates_sample_bounds() {
+        let options = |min_samples, max_samples| BenchOptions {
+            min_samples,
+            max_samples,
+            ..BenchOptions::default()
+        };
+        assert!(options(10, 10).validate().is_ok());
+        assert!(options(0, 10).validate().is_err());
+        assert!(options(11, 10).validate().is_err());
+    }
+
+    #[test]
+    fn labels_cpu_time() {
+        let cpu = CpuTime {
+            user: Duration::from_micros(3),
+            system: Duration::from_micros(1),
+        };
+        let wall = Duration::from_micros(5);
+        assert_eq!(
+            format_cpu(&wall, &cpu, None),
+            format!("  {ANSI_ITALIC}wall 5.0µs · cpu 4.0µs (user 3.0µs · sys 1.0µs){ANSI_RESET}")
+        );
+        assert_eq!(
+            format_cpu(&wall, &cpu, Some(Statistic::Median)),
+            format!("  {ANSI_ITALIC}wall median 5.0µs · cpu mean 4.0µs (user 3.0µs · sys 1.0µs){ANSI_RESET}")
+        );
+    }
+
+    #[test]
+    fn selects_parts() {
+        assert_eq!(parse_part("2"), Ok(2));
+        assert!(parse_part("3").is_err());
+
+        let options = RunOptions {
+            part: Some(2),
+            ..RunOptions::default()
+        };
+        assert!(!options.runs_part(1));
+        assert!(options.runs_part(2));
+        assert!(RunOptions::default().runs_part(1));
+        assert_eq!(options.to_args(), ["--part", "2"]);
+    }
 }
@@ -0,0 +1,94 @@
+/// Named timing spans that solutions can use to mark phases such as parsing and solving.
+/// Spans are recorded per thread and collected by the runner after each run of a part.
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static RECORDED: RefCell<Vec<(&'static str, Duration)>> = const { RefCell::new(vec![]) };
+}
+
+/// A guard that records the time between its creation and drop under `name`.
+///
+/// ```
+/// let _span = advent_of_code::template::spans::span("parse");
+/// ```
+#[must_use = "the span is recorded when the guard is dropped"]
+pub struct Span {
+    name: &'static str,
+    start: Instant,
+}
+
+/// Starts a new named span, see [`Span`].
+pub fn span(name: &'static str) -> Span {
+    Span {
+        name,
+        start: Instant::now(),
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        record(self.name, self.start.elapsed());
+    }
+}
+
+fn record(name: &'static str, duration: Duration) {
+    RECORDED.with(|recorded| {
+        let mut recorded = recorded.borrow_mut();
+        match recorded.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, total)) => *total += duration,
+            None => recorded.push((name, duration)),
+        }
+    });
+}
+
+/// Discards all spans recorded on the current thread.
+pub fn reset() {
+    RECORDED.with(|recorded| recorded.borrow_mut().clear());
+}
+
+/// Returns the total time spent in each span since the last [`reset`], in order of first use.
+pub fn take() -> Vec<(String, Duration)> {
+    RECORDED.with(|recorded| {
+        recorded
+            .borrow_mut()
+            .drain(..)
+            .map(|(name, duration)| (name.to_string(), duration))
+            .collect()
+    })
+}
+
+/// Evaluates an expression inside a named span and returns its value.
+///
+/// ```
+/// let lines = advent_of_code::span!("parse", "a\nb".lines().count());
+/// ```
+#[macro_export]
+macro_rules! span {
+    ($name:expr, $body:expr) => {{
+        let _span = $crate::template::spans::span($name);
+        $body
+    }};
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{reset, span, take};
+
+    #[test]
+    fn records_spans_in_order() {
+        reset();
+        {
+            let _a = span("parse");
+        }
+        let value = crate::span!("solve", 40 + 2);
+        {
+            let _b = span("parse");
+        }
+
+        assert_eq!(value, 42);
+        let names: Vec<String> = take().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(names, vec!["parse", "solve"]);
+        assert!(take().is_empty());
+    }
+}
@@ -0,0 +1,247 @@
+/// Summary statistics over the samples collected while benchmarking a solution part.
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+/// The statistic used when a single duration has to represent a benchmark.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Statistic {
+    #[default]
+    Mean,
+    Median,
+    Min,
+    Max,
+    P95,
+    P99,
+}
+
+impl Statistic {
+    #[must_use]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Statistic::Mean => "mean",
+            Statistic::Median => "median",
+            Statistic::Min => "min",
+            Statistic::Max => "max",
+            Statistic::P95 => "p95",
+            Statistic::P99 => "p99",
+        }
+    }
+}
+
+impl Display for Statistic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Statistic {
+    type Err = StatisticFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(Statistic::Mean),
+            "median" => Ok(Statistic::Median),
+            "min" => Ok(Statistic::Min),
+            "max" => Ok(Statistic::Max),
+            "p95" => Ok(Statistic::P95),
+            "p99" => Ok(Statistic::P99),
+            _ => Err(StatisticFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`Statistic`].
+#[derive(Debug)]
+pub struct StatisticFromStrError;
+
+impl std::error::Error for StatisticFromStrError {}
+
+impl Display for StatisticFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of: mean, median, min, max, p95, p99")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Summary {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+    pub p99: Duration,
+    /// Number of samples that were discarded as outliers.
+    pub outliers: usize,
+}
+
+impl Summary {
+    /// Computes the summary of a non-empty list of samples.
+    /// If `reject_outliers` is set, samples outside of 1.5 times the interquartile range are discarded first.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration], reject_outliers: bool) -> Self {
+        assert!(!samples.is_empty(), "cannot summarize an empty benchmark");
+
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let mut outliers = 0;
+
+        if reject_outliers && sorted.len() >= 4 {
+            let q1 = percentile(&sorted, 25.0);
+            let q3 = percentile(&sorted, 75.0);
+            let fence = (q3 - q1) * 3 / 2;
+            let (low, high) = (q1.saturating_sub(fence), q3 + fence);
+
+            let len = sorted.len();
+            sorted.retain(|&x| x >= low && x <= high);
+            outliers = len - sorted.len();
+        }
+
+        let n = sorted.len() as u128;
+        let mean = sorted.iter().sum::<u128>() / n;
+
+        let median = if sorted.len().is_multiple_of(2) {
+            (sorted[sorted.len() / 2 - 1] + sorted[sorted.len() / 2]) / 2
+        } else {
+            sorted[sorted.len() / 2]
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted
+            .iter()
+            .map(|&x| (x as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Self {
+            mean: from_nanos(mean),
+            median: from_nanos(median),
+            min: from_nanos(sorted[0]),
+            max: from_nanos(sorted[sorted.len() - 1]),
+            stddev: Duration::from_secs_f64(variance.sqrt() / 1e9),
+            p95: from_nanos(percentile(&sorted, 95.0)),
+            p99: from_nanos(percentile(&sorted, 99.0)),
+            outliers,
+        }
+    }
+
+    #[must_use]
+    pub fn get(&self, statistic: Statistic) -> Duration {
+        match statistic {
+            Statistic::Mean => self.mean,
+            Statistic::Median => self.median,
+            Statistic::Min => self.min,
+            Statistic::Max => self.max,
+            Statistic::P95 => self.p95,
+            Statistic::P99 => self.p99,
+        }
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> Value {
+        let nanos = |d: Duration| Value::from(d.as_nanos());
+        json!({
+            "mean_nanos": nanos(self.mean),
+            "median_nanos": nanos(self.median),
+            "min_nanos": nanos(self.min),
+            "max_nanos": nanos(self.max),
+            "stddev_nanos": nanos(self.stddev),
+            "p95_nanos": nanos(self.p95),
+            "p99_nanos": nanos(self.p99),
+            "outliers": self.outliers,
+        })
+    }
+
+    #[must_use]
+    pub fn from_json(value: &Value) -> Option<Self> {
+        let nanos = |key: &str| value.get(key)?.as_u64().map(Duration::from_nanos);
+        Some(Self {
+            mean: nanos("mean_nanos")?,
+            median: nanos("median_nanos")?,
+            min: nanos("min_nanos")?,
+            max: nanos("max_nanos")?,
+            stddev: nanos("stddev_nanos")?,
+            p95: nanos("p95_nanos")?,
+            p99: nanos("p99_nanos")?,
+            outliers: usize::try_from(value.get("outliers")?.as_u64()?).ok()?,
+        })
+    }
+}
+
+/// Nearest-rank percentile of an ascending list.
+fn percentile(sorted: &[u128], p: f64) -> u128 {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Statistic, Summary};
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn summarizes_samples() {
+        let summary = Summary::from_samples(&samples(&[5, 1, 4, 2, 3]), false);
+        assert_eq!(summary.mean, Duration::from_nanos(3));
+        assert_eq!(summary.median, Duration::from_nanos(3));
+        assert_eq!(summary.min, Duration::from_nanos(1));
+        assert_eq!(summary.max, Duration::from_nanos(5));
+        assert_eq!(summary.p95, Duration::from_nanos(5));
+        assert_eq!(summary.stddev.as_nanos(), 1);
+        assert_eq!(summary.outliers, 0);
+    }
+
+    #[test]
+    fn median_of_even_samples() {
+        let summary = Summary::from_samples(&samples(&[10, 20, 30, 40]), false);
+        assert_eq!(summary.get(Statistic::Median), Duration::from_nanos(25));
+    }
+
+    #[test]
+    fn percentiles() {
+        let nanos: Vec<u64> = (1..=100).collect();
+        let summary = Summary::from_samples(&samples(&nanos), false);
+        assert_eq!(summary.p95, Duration::from_nanos(95));
+        assert_eq!(summary.p99, Duration::from_nanos(99));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let raw = samples(&[10, 11, 10, 12, 11, 10, 1000]);
+
+        let summary = Summary::from_samples(&raw, false);
+        assert_eq!(summary.max, Duration::from_nanos(1000));
+
+        let summary = Summary::from_samples(&raw, true);
+        assert_eq!(summary.outliers, 1);
+        assert_eq!(summary.max, Duration::from_nanos(12));
+        assert_eq!(summary.mean, Duration::from_nanos(10));
+    }
+
+    #[test]
+    fn json_roundtrip() {
+        let summary = Summary::from_samples(&samples(&[3, 9, 27]), false);
+        assert_eq!(Summary::from_json(&summary.to_json()), Some(summary));
+    }
+}
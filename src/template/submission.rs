@@ -0,0 +1,440 @@
+/// Interprets the responses of the website to submitted answers and keeps a history of them.
+/// The history of a day is kept as JSON in `data/submissions/<day>.json`.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{fs, io};
+
+use serde_json::{json, Value};
+
+use crate::template::paths;
+use crate::{Day, Year};
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Parser(e.to_string())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(msg) => write!(f, "could not parse submission history: {msg}"),
+            Error::IO(e) => write!(f, "could not access submission history: {e}"),
+        }
+    }
+}
+
+/// The verdict of the website on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// The answer is wrong, without a hint in which direction.
+    Wrong,
+    /// The previous answer was submitted too recently, the answer was not checked.
+    WaitFor(Duration),
+    /// The part was already solved, the answer was not checked.
+    AlreadyCompleted,
+}
+
+impl Verdict {
+    /// Parses the message of a submission response, as printed by aoc-cli or extracted by
+    /// [`super::client::Client::submit`]. Returns [`None`] for messages it does not recognize.
+    #[must_use]
+    pub fn parse(message: &str) -> Option<Self> {
+        // the website separates sentences with two spaces, html rendering might collapse them.
+        let message = message.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        if message.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if message.contains("your answer is too high") {
+            Some(Verdict::TooHigh)
+        } else if message.contains("your answer is too low") {
+            Some(Verdict::TooLow)
+        } else if message.contains("That's not the right answer") {
+            Some(Verdict::Wrong)
+        } else if message.contains("You gave an answer too recently") {
+            let wait = message
+                .split_once("You have ")
+                .and_then(|(_, rest)| rest.split_once(" left to wait"))
+                .and_then(|(wait, _)| parse_wait(wait))
+                .unwrap_or_default();
+            Some(Verdict::WaitFor(wait))
+        } else if message.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadyCompleted)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the website checked the answer.
+    #[must_use]
+    pub fn is_checked(&self) -> bool {
+        !matches!(self, Verdict::WaitFor(_) | Verdict::AlreadyCompleted)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "wrong, too high"),
+            Verdict::TooLow => write!(f, "wrong, too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::WaitFor(wait) => {
+                write!(
+                    f,
+                    "answered too recently, {} left to wait",
+                    format_wait(*wait)
+                )
+            }
+            Verdict::AlreadyCompleted => write!(f, "already completed"),
+        }
+    }
+}
+
+/// An answer that was submitted with `--submit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn now(part: u8, answer: &str, verdict: Verdict) -> Self {
+        Self {
+            part,
+            answer: answer.trim().to_string(),
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            verdict,
+        }
+    }
+}
+
+/// Reasons not to submit an answer, because the website would not accept it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was solved with this answer.
+    Solved(String),
+    /// The answer was submitted before and was wrong.
+    Known(Verdict),
+    /// The answer is at least as high as an answer that was too high.
+    AboveBound(String),
+    /// The answer is at most as low as an answer that was too low.
+    BelowBound(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "already solved with `{answer}`"),
+            Refusal::Known(verdict) => write!(f, "submitted before, {verdict}"),
+            Refusal::AboveBound(bound) => write!(f, "too high, `{bound}` already was"),
+            Refusal::BelowBound(bound) => write!(f, "too low, `{bound}` already was"),
+        }
+    }
+}
+
+/// The submissions of a day, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct History {
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    /// Checks an answer against the previous submissions of its part.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), Refusal> {
+        let answer = answer.trim();
+        let submissions: Vec<&Submission> =
+            self.submissions.iter().filter(|s| s.part == part).collect();
+
+        if let Some(correct) = submissions.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Refusal::Solved(correct.answer.clone()));
+        }
+
+        if let Some(known) = submissions
+            .iter()
+            .find(|s| s.answer == answer && s.verdict.is_checked())
+        {
+            return Err(Refusal::Known(known.verdict));
+        }
+
+        // bounds only apply to numeric answers.
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+        let bound = |verdict: Verdict| {
+            submissions
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+        };
+
+        if let Some((_, high)) = bound(Verdict::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min()
+        {
+            return Err(Refusal::AboveBound(high.clone()));
+        }
+
+        if let Some((_, low)) = bound(Verdict::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max()
+        {
+            return Err(Refusal::BelowBound(low.clone()));
+        }
+
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let submissions: Vec<Value> = self
+            .submissions
+            .iter()
+            .map(|s| {
+                let (verdict, wait) = match s.verdict {
+                    Verdict::Correct => ("correct", None),
+                    Verdict::TooHigh => ("too_high", None),
+                    Verdict::TooLow => ("too_low", None),
+                    Verdict::Wrong => ("wrong", None),
+                    Verdict::WaitFor(wait) => ("wait", Some(wait.as_secs())),
+                    Verdict::AlreadyCompleted => ("already_completed", None),
+                };
+
+                json!({
+                    "part": s.part,
+                    "answer": s.answer,
+                    "timestamp": s.timestamp,
+                    "verdict": verdict,
+                    "wait_secs": wait,
+                })
+            })
+            .collect();
+
+        json!({"submissions": submissions}).to_string()
+    }
+
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let value = serde_json::from_str::<Value>(s)?;
+        let invalid = |field: &str| Error::Parser(format!("missing or invalid field `{field}`"));
+
+        let submissions = value
+            .get("submissions")
+            .and_then(Value::as_array)
+            .ok_or_else(|| invalid("submissions"))?
+            .iter()
+            .map(|entry| {
+                let part = entry
+                    .get("part")
+                    .and_then(Value::as_u64)
+                    .and_then(|p| u8::try_from(p).ok())
+                    .ok_or_else(|| invalid("part"))?;
+
+                let answer = entry
+                    .get("answer")
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| invalid("answer"))?;
+
+                let timestamp = entry
+                    .get("timestamp")
+                    .and_then(Value::as_u64)
+                    .ok_or_else(|| invalid("timestamp"))?;
+
+                let wait = entry
+                    .get("wait_secs")
+                    .and_then(Value::as_u64)
+                    .map(Duration::from_secs);
+
+                let verdict = match entry.get("verdict").and_then(Value::as_str) {
+                    Some("correct") => Verdict::Correct,
+                    Some("too_high") => Verdict::TooHigh,
+                    Some("too_low") => Verdict::TooLow,
+                    Some("wrong") => Verdict::Wrong,
+                    Some("wait") => Verdict::WaitFor(wait.unwrap_or_default()),
+                    Some("already_completed") => Verdict::AlreadyCompleted,
+                    _ => return Err(invalid("verdict")),
+                };
+
+                Ok(Submission {
+                    part,
+                    answer: answer.to_string(),
+                    timestamp,
+                    verdict,
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self { submissions })
+    }
+}
+
+#[must_use]
+pub fn get_path(year: Option<Year>, day: Day) -> PathBuf {
+    paths::data_dir(year)
+        .join("submissions")
+        .join(format!("{day}.json"))
+}
+
+/// Loads the history of a day, which is empty if nothing was submitted yet.
+pub fn load(year: Option<Year>, day: Day) -> Result<History, Error> {
+    match fs::read_to_string(get_path(year, day)) {
+        Ok(s) => History::parse(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Appends a submission to the history of a day.
+pub fn record(year: Option<Year>, day: Day, submission: Submission) -> Result<(), Error> {
+    let mut history = load(year, day)?;
+    history.submissions.push(submission);
+
+    let path = get_path(year, day);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, history.to_json() + "\n")?;
+    Ok(())
+}
+
+/// Parses a wait time as displayed by the website, e.g. `42s` or `1m 42s`.
+fn parse_wait(s: &str) -> Option<Duration> {
+    let mut secs = 0;
+    for token in s.split_whitespace() {
+        let unit_at = token.find(|c: char| !c.is_ascii_digit())?;
+        let (value, unit) = token.split_at(unit_at);
+        let value: u64 = value.parse().ok()?;
+        secs += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// Formats a wait time the way the website does, see [`parse_wait`].
+#[must_use]
+pub fn format_wait(wait: Duration) -> String {
+    let secs = wait.as_secs();
+    match (secs / 60, secs % 60) {
+        (0, secs) => format!("{secs}s"),
+        (mins, secs) => format!("{mins}m {secs}s"),
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_wait, History, Refusal, Submission, Verdict};
+    use crate::template::puzzle;
+
+    fn parse_fixture(html: &str) -> Option<Verdict> {
+        Verdict::parse(&puzzle::to_text(puzzle::articles(html)[0]))
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let fixtures = [
+            (
+                include_str!("fixtures/submit/correct.html"),
+                Verdict::Correct,
+            ),
+            (
+                include_str!("fixtures/submit/too_high.html"),
+                Verdict::TooHigh,
+            ),
+            (
+                include_str!("fixtures/submit/too_low.html"),
+                Verdict::TooLow,
+            ),
+            (include_str!("fixtures/submit/wrong.html"), Verdict::Wrong),
+            (
+                include_str!("fixtures/submit/wait.html"),
+                Verdict::WaitFor(Duration::from_secs(102)),
+            ),
+            (
+                include_str!("fixtures/submit/already_completed.html"),
+                Verdict::AlreadyCompleted,
+            ),
+        ];
+
+        for (html, verdict) in fixtures {
+            assert_eq!(parse_fixture(html), Some(verdict));
+        }
+
+        assert_eq!(Verdict::parse("Internal Server Error"), None);
+    }
+
+    #[test]
+    fn guards_against_known_answers() {
+        let submission = |part, answer: &str, verdict| Submission {
+            part,
+            answer: answer.into(),
+            timestamp: 1_701_993_600,
+            verdict,
+        };
+        let history = History {
+            submissions: vec![
+                submission(1, "abc", Verdict::Wrong),
+                submission(1, "100", Verdict::TooHigh),
+                submission(1, "80", Verdict::TooHigh),
+                submission(1, "20", Verdict::TooLow),
+                submission(1, "50", Verdict::WaitFor(Duration::from_secs(30))),
+                submission(2, "7", Verdict::Correct),
+            ],
+        };
+
+        assert_eq!(history.check(1, "abc"), Err(Refusal::Known(Verdict::Wrong)));
+        assert_eq!(
+            history.check(1, "90"),
+            Err(Refusal::AboveBound("80".into()))
+        );
+        assert_eq!(
+            history.check(1, "80"),
+            Err(Refusal::Known(Verdict::TooHigh))
+        );
+        assert_eq!(
+            history.check(1, "-3"),
+            Err(Refusal::BelowBound("20".into()))
+        );
+        assert_eq!(history.check(1, "50"), Ok(()));
+        assert_eq!(history.check(1, "def"), Ok(()));
+        assert_eq!(history.check(2, "8"), Err(Refusal::Solved("7".into())));
+
+        assert_eq!(History::parse(&history.to_json()).unwrap(), history);
+    }
+
+    #[test]
+    fn formats_wait_times() {
+        assert_eq!(format_wait(Duration::from_secs(42)), "42s");
+        assert_eq!(format_wait(Duration::from_secs(102)), "1m 42s");
+        assert_eq!(
+            Verdict::WaitFor(Duration::from_secs(5)).to_string(),
+            "answered too recently, 5s left to wait"
+        );
+    }
+}
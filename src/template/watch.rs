@@ -0,0 +1,136 @@
+/// Detects changes to the files that affect a day's solution, for `solve --watch`.
+/// The standard library has no file system notifications, so modification times are polled.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::template::paths;
+use crate::{Day, Year};
+
+/// Modification times of a set of files, [`None`] for files that could not be read.
+pub type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// The files that affect the solution of `day`: its binary, the library and its inputs and examples.
+#[must_use]
+pub fn watched_files(year: Option<Year>, day: Day) -> Vec<PathBuf> {
+    let mut files = vec![];
+    collect_sources(Path::new("src"), &mut files);
+    files.push(paths::bin_path(year, day));
+
+    // inputs and examples, including named examples such as `DD-2.txt`.
+    let data_dirs = fs::read_dir(paths::data_dir(year))
+        .into_iter()
+        .flatten()
+        .flatten();
+    for dir in data_dirs {
+        for entry in fs::read_dir(dir.path()).into_iter().flatten().flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with(&day.to_string()) && name.ends_with(".txt") {
+                files.push(entry.path());
+            }
+        }
+    }
+
+    files
+}
+
+/// Collects the rust files below `dir`, except for the binaries of other days.
+fn collect_sources(dir: &Path, files: &mut Vec<PathBuf>) {
+    if dir.ends_with("bin") {
+        return;
+    }
+
+    for entry in fs::read_dir(dir).into_iter().flatten().flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_sources(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+#[must_use]
+pub fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .map(|path| {
+            let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+            (path.clone(), modified)
+        })
+        .collect()
+}
+
+/// Polls the files returned by `files` until one of them is modified, created or deleted.
+pub struct Watcher<F: Fn() -> Vec<PathBuf>> {
+    files: F,
+    last: Snapshot,
+    interval: Duration,
+}
+
+impl<F: Fn() -> Vec<PathBuf>> Watcher<F> {
+    pub fn new(files: F, interval: Duration) -> Self {
+        let last = snapshot(&files());
+        Self {
+            files,
+            last,
+            interval,
+        }
+    }
+
+    /// Takes a new snapshot and returns whether it differs from the previous one.
+    pub fn has_changed(&mut self) -> bool {
+        let current = snapshot(&(self.files)());
+        let changed = current != self.last;
+        self.last = current;
+        changed
+    }
+
+    /// Blocks until a file changed.
+    pub fn wait(&mut self) {
+        while !self.has_changed() {
+            thread::sleep(self.interval);
+        }
+
+        // editors often save in several steps, wait for them to settle before reporting the change.
+        thread::sleep(self.interval);
+        self.has_changed();
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::fs::{self, File};
+    use std::time::{Duration, SystemTime};
+
+    use super::Watcher;
+
+    #[test]
+    fn detects_changes() {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("08.txt");
+        fs::write(&file, "a").unwrap();
+
+        let watched = file.clone();
+        let mut watcher = Watcher::new(move || vec![watched.clone()], Duration::ZERO);
+        assert!(!watcher.has_changed());
+
+        File::options()
+            .write(true)
+            .open(&file)
+            .unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+        assert!(watcher.has_changed());
+        assert!(!watcher.has_changed());
+
+        fs::remove_file(&file).unwrap();
+        assert!(watcher.has_changed());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}